@@ -0,0 +1,143 @@
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+#[derive(Parser)]
+#[command(
+    name = "imagevault",
+    version,
+    about = "Manage an ImageVault vault from the terminal"
+)]
+pub struct Cli {
+    /// Vault folder to use instead of the app's active vault
+    #[arg(long, global = true, env = "IMAGEVAULT_VAULT")]
+    pub vault: Option<PathBuf>,
+
+    /// Tesseract executable used for OCR
+    #[arg(long, global = true, env = "IMAGEVAULT_TESSERACT")]
+    pub tesseract: Option<PathBuf>,
+
+    /// Print machine-readable JSON instead of text
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Add image files to the vault
+    Add {
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+
+        /// Move the files instead of copying them
+        #[arg(long = "move")]
+        move_files: bool,
+    },
+    /// Import every image in a folder
+    ImportDir {
+        dir: PathBuf,
+
+        /// Move the files instead of copying them
+        #[arg(long = "move")]
+        move_files: bool,
+    },
+    /// Download an image into the vault
+    Fetch { url: String },
+    /// Search images by tag or OCR text, or list all images.
+    /// Filters: tag:, text:, source:, format:, after:YYYY-MM-DD, before:YYYY-MM-DD,
+    /// is:favorite, rating:[>=]N, label:COLOR|none, sort:[-]added|filename|rating|favorite|label
+    Search { query: Vec<String> },
+    /// Manage tags on an image
+    #[command(subcommand)]
+    Tag(TagCommand),
+    /// Run text recognition again
+    #[command(subcommand)]
+    Ocr(OcrCommand),
+    /// Export images to a folder or ZIP archive
+    Export(ExportArgs),
+    /// Verify the database and image files
+    Check,
+}
+
+#[derive(Subcommand)]
+pub enum TagCommand {
+    /// Add tags to an image
+    Add {
+        id: i64,
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
+    /// Remove tags from an image
+    Remove {
+        id: i64,
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
+    /// List the tags of an image
+    List { id: i64 },
+    /// List every tag in the vault with its usage count
+    Stats,
+    /// Rename a tag and its child tags on every image
+    Rename { from: String, to: String },
+}
+
+#[derive(Subcommand)]
+pub enum OcrCommand {
+    /// Re-run OCR for the given images
+    Retry {
+        #[arg(required = true)]
+        ids: Vec<i64>,
+    },
+}
+
+#[derive(Args)]
+pub struct ExportArgs {
+    pub destination: PathBuf,
+
+    /// Image ids to export
+    #[arg(long, value_delimiter = ',', conflicts_with = "query")]
+    pub ids: Vec<i64>,
+
+    /// Search query selecting the images to export
+    #[arg(long)]
+    pub query: Option<String>,
+
+    /// Write a ZIP archive instead of a folder
+    #[arg(long)]
+    pub zip: bool,
+
+    /// Convert images to this format (png, jpg, webp, ...)
+    #[arg(long)]
+    pub format: Option<String>,
+
+    /// Filename template using {id}, {index}, {stem} and {date}
+    #[arg(long, default_value = "{stem}")]
+    pub template: String,
+
+    #[arg(long)]
+    pub max_width: Option<u32>,
+
+    #[arg(long)]
+    pub max_height: Option<u32>,
+
+    /// Remove EXIF, XMP and other embedded metadata
+    #[arg(long)]
+    pub strip_metadata: bool,
+
+    /// Draw image annotations into the exported copies
+    #[arg(long)]
+    pub render_annotations: bool,
+
+    #[arg(long, value_enum, default_value_t = ManifestArg::Json)]
+    pub manifest: ManifestArg,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ManifestArg {
+    Json,
+    Csv,
+    None,
+}
@@ -0,0 +1,278 @@
+mod cli;
+
+use std::{process::ExitCode, sync::Arc};
+
+use clap::Parser;
+use cli::{Cli, Command, ExportArgs, ManifestArg, OcrCommand, TagCommand};
+use serde::Serialize;
+use vault_core::{
+    Vault, VaultConfig,
+    models::{
+        export::{ExportOptions, ExportSelection, ExportTarget, ManifestFormat},
+        image::Image,
+    },
+    utils::{config_utils, export_utils, path_utils},
+};
+
+#[derive(Serialize)]
+struct ItemResult {
+    item: String,
+    ok: bool,
+    detail: String,
+}
+
+impl ItemResult {
+    fn from_result<T>(
+        item: impl ToString,
+        result: Result<T, Box<dyn std::error::Error>>,
+        detail: impl FnOnce(T) -> String,
+    ) -> Self {
+        match result {
+            Ok(value) => ItemResult {
+                item: item.to_string(),
+                ok: true,
+                detail: detail(value),
+            },
+            Err(e) => ItemResult {
+                item: item.to_string(),
+                ok: false,
+                detail: e.to_string(),
+            },
+        }
+    }
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<(), Box<dyn std::error::Error>> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+fn print_images(json: bool, images: &[Image]) -> Result<(), Box<dyn std::error::Error>> {
+    if json {
+        return print_json(&images);
+    }
+
+    for image in images {
+        println!("{}\t{}\t{}", image.id, image.added_at, image.path);
+    }
+    Ok(())
+}
+
+fn print_results(json: bool, results: &[ItemResult]) -> Result<bool, Box<dyn std::error::Error>> {
+    if json {
+        print_json(&results)?;
+    } else {
+        for result in results {
+            let status = if result.ok { "ok" } else { "failed" };
+            println!("{}\t{}\t{}", status, result.item, result.detail);
+        }
+    }
+
+    Ok(results.iter().all(|result| result.ok))
+}
+
+fn open_vault(cli: &Cli, sync: bool) -> Result<Vault, Box<dyn std::error::Error>> {
+    let config = config_utils::load_config()?;
+    let root = match &cli.vault {
+        Some(root) if !root.join(path_utils::DB_FILE).exists() => {
+            return Err(format!("{} does not contain a vault", root.display()).into());
+        }
+        Some(root) => root.clone(),
+        None => config_utils::resolve_vault_root()?,
+    };
+
+    let config = VaultConfig {
+        root,
+        tesseract_path: cli.tesseract.clone(),
+        network: config.network,
+    };
+    if sync {
+        Vault::open(config)
+    } else {
+        Vault::load(config)
+    }
+}
+
+fn export(vault: &Vault, json: bool, args: ExportArgs) -> Result<bool, Box<dyn std::error::Error>> {
+    let selection = if args.ids.is_empty() {
+        ExportSelection::Query(args.query.unwrap_or_default())
+    } else {
+        ExportSelection::Ids(args.ids)
+    };
+    let options = ExportOptions {
+        target: if args.zip {
+            ExportTarget::Zip
+        } else {
+            ExportTarget::Folder
+        },
+        filename_template: args.template,
+        format: args.format,
+        max_width: args.max_width,
+        max_height: args.max_height,
+        strip_metadata: args.strip_metadata,
+        render_annotations: args.render_annotations,
+        manifest: match args.manifest {
+            ManifestArg::Json => ManifestFormat::Json,
+            ManifestArg::Csv => ManifestFormat::Csv,
+            ManifestArg::None => ManifestFormat::None,
+        },
+    };
+
+    let summary =
+        export_utils::export_images(vault, selection, &args.destination, &options, |_, _| {})?;
+
+    if json {
+        print_json(&summary)?;
+    } else {
+        println!(
+            "Exported {} images to {}",
+            summary.exported, summary.destination
+        );
+        for failure in &summary.failed {
+            println!("failed\t{}\t{}", failure.id, failure.error);
+        }
+    }
+
+    Ok(summary.failed.is_empty())
+}
+
+fn run(cli: Cli) -> Result<bool, Box<dyn std::error::Error>> {
+    let imports = matches!(
+        cli.command,
+        Command::Add { .. } | Command::ImportDir { .. } | Command::Fetch { .. }
+    );
+    let vault = Arc::new(open_vault(&cli, imports)?);
+    let json = cli.json;
+
+    match cli.command {
+        Command::Add { paths, move_files } => {
+            let results: Vec<_> = paths
+                .iter()
+                .map(|path| {
+                    ItemResult::from_result(
+                        path.display(),
+                        vault.import_file(path, move_files),
+                        |image| image.path,
+                    )
+                })
+                .collect();
+            print_results(json, &results)
+        }
+        Command::ImportDir { dir, move_files } => {
+            let imported = vault.import_folder(&dir, move_files, |count, total| {
+                if !json {
+                    eprintln!("Imported {}/{}", count, total);
+                }
+            })?;
+            let results: Vec<_> = imported
+                .iter()
+                .map(|image| ItemResult {
+                    item: image.path.clone(),
+                    ok: true,
+                    detail: image.id.to_string(),
+                })
+                .collect();
+            print_results(json, &results)
+        }
+        Command::Fetch { url } => {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()?;
+            let result = runtime.block_on(vault.fetch_url(&url));
+            let results = [ItemResult::from_result(&url, result, |image| image.path)];
+            print_results(json, &results)
+        }
+        Command::Search { query } => {
+            let query = query.join(" ");
+            let images = if query.trim().is_empty() {
+                vault.images()?
+            } else {
+                vault.search(&query)?
+            };
+            print_images(json, &images)?;
+            Ok(true)
+        }
+        Command::Tag(TagCommand::Add { id, tags }) => {
+            let results: Vec<_> = tags
+                .iter()
+                .map(|tag| ItemResult::from_result(tag, vault.add_tag(id, tag), |_| id.to_string()))
+                .collect();
+            print_results(json, &results)
+        }
+        Command::Tag(TagCommand::Remove { id, tags }) => {
+            let results: Vec<_> = tags
+                .iter()
+                .map(|tag| {
+                    ItemResult::from_result(tag, vault.remove_tag(id, tag), |_| id.to_string())
+                })
+                .collect();
+            print_results(json, &results)
+        }
+        Command::Tag(TagCommand::List { id }) => {
+            let tags = vault.tags(id)?;
+            if json {
+                print_json(&tags)?;
+            } else {
+                println!("{}", tags.join("\n"));
+            }
+            Ok(true)
+        }
+        Command::Tag(TagCommand::Stats) => {
+            let tags = vault.list_tags()?;
+            if json {
+                print_json(&tags)?;
+            } else {
+                for tag in tags {
+                    println!("{}\t{}", tag.count, tag.tag);
+                }
+            }
+            Ok(true)
+        }
+        Command::Tag(TagCommand::Rename { from, to }) => {
+            let result = vault.rename_tag(&from, &to);
+            print_results(json, &[ItemResult::from_result(&from, result, |_| to)])
+        }
+        Command::Ocr(OcrCommand::Retry { ids }) => {
+            let results: Vec<_> = ids
+                .iter()
+                .map(|id| ItemResult::from_result(id, vault.ocr_retry(*id), |text| text))
+                .collect();
+            print_results(json, &results)
+        }
+        Command::Export(args) => export(&vault, json, args),
+        Command::Check => {
+            let report = vault.check()?;
+            let healthy = report.integrity == "ok"
+                && report.missing_files.is_empty()
+                && report.untracked_files.is_empty();
+
+            if json {
+                print_json(&report)?;
+            } else {
+                println!("Database integrity: {}", report.integrity);
+                println!("Images: {}", report.image_count);
+                for image in &report.missing_files {
+                    println!("missing\t{}\t{}", image.id, image.path);
+                }
+                for path in &report.untracked_files {
+                    println!("untracked\t{}", path);
+                }
+            }
+
+            Ok(healthy)
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match run(cli) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
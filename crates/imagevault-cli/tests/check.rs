@@ -0,0 +1,32 @@
+use std::process::Command;
+
+use vault_core::{Vault, VaultConfig};
+
+#[test]
+fn check_reports_untracked_files_without_importing_them() {
+    let root = std::env::temp_dir().join(format!("imagevault-cli-check-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(&root).unwrap();
+    let vault = Vault::create(VaultConfig::new(&root)).unwrap();
+    let untracked = vault.image_dir().join("untracked.png");
+    drop(vault);
+    std::fs::write(&untracked, b"not tracked").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_imagevault"))
+        .arg("--vault")
+        .arg(&root)
+        .args(["--json", "check"])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["image_count"], 0);
+    assert_eq!(
+        report["untracked_files"],
+        serde_json::json!([untracked.to_string_lossy()])
+    );
+
+    let vault = Vault::load(VaultConfig::new(&root)).unwrap();
+    assert!(vault.images().unwrap().is_empty());
+}
@@ -0,0 +1,332 @@
+use crate::db::database::{IMAGE_COLUMNS, count_images, image_from_row};
+use crate::models::album::{Album, AlbumFolder, AlbumInput, SmartAlbum, SmartAlbumInput};
+use crate::models::image::Image;
+use crate::models::search::SearchQuery;
+use rusqlite::{Connection, OptionalExtension, Result, params};
+use std::path::Path;
+
+const ALBUM_COLUMNS: &str = "albums.id, albums.name, albums.description,
+    COALESCE(albums.cover_image_id,
+        (SELECT image_id FROM album_images WHERE album_id = albums.id
+         ORDER BY position LIMIT 1)),
+    albums.folder_id,
+    (SELECT COUNT(*) FROM album_images WHERE album_id = albums.id),
+    albums.created_at";
+
+fn album_from_row(row: &rusqlite::Row) -> Result<Album> {
+    Ok(Album {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        description: row.get(2)?,
+        cover_image_id: row.get(3)?,
+        folder_id: row.get(4)?,
+        image_count: row.get::<_, i64>(5)? as usize,
+        created_at: row.get(6)?,
+    })
+}
+
+pub fn get_albums(conn: &Connection) -> Result<Vec<Album>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM albums ORDER BY albums.name COLLATE NOCASE",
+        ALBUM_COLUMNS
+    ))?;
+    stmt.query_map([], album_from_row)?.collect()
+}
+
+pub fn get_album(conn: &Connection, album_id: i64) -> Result<Album> {
+    conn.query_row(
+        &format!("SELECT {} FROM albums WHERE albums.id = ?1", ALBUM_COLUMNS),
+        [album_id],
+        album_from_row,
+    )
+}
+
+pub fn get_image_albums(conn: &Connection, image_id: i64) -> Result<Vec<Album>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM albums
+         JOIN album_images ON album_images.album_id = albums.id
+         WHERE album_images.image_id = ?1
+         ORDER BY albums.name COLLATE NOCASE",
+        ALBUM_COLUMNS
+    ))?;
+    stmt.query_map([image_id], album_from_row)?.collect()
+}
+
+pub fn insert_album(conn: &Connection, album: &AlbumInput) -> Result<i64> {
+    conn.execute(
+        "INSERT INTO albums (name, description, cover_image_id, folder_id)
+         VALUES (?1, ?2, ?3, ?4)",
+        params![
+            album.name.trim(),
+            album.description,
+            album.cover_image_id,
+            album.folder_id
+        ],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+pub fn update_album(conn: &Connection, album_id: i64, album: &AlbumInput) -> Result<usize> {
+    conn.execute(
+        "UPDATE albums SET name = ?1, description = ?2, cover_image_id = ?3, folder_id = ?4
+         WHERE id = ?5",
+        params![
+            album.name.trim(),
+            album.description,
+            album.cover_image_id,
+            album.folder_id,
+            album_id
+        ],
+    )
+}
+
+pub fn rename_album(conn: &Connection, album_id: i64, name: &str) -> Result<usize> {
+    conn.execute(
+        "UPDATE albums SET name = ?1 WHERE id = ?2",
+        params![name.trim(), album_id],
+    )
+}
+
+pub fn delete_album(conn: &Connection, album_id: i64) -> Result<()> {
+    conn.execute("DELETE FROM album_images WHERE album_id = ?1", [album_id])?;
+    conn.execute("DELETE FROM albums WHERE id = ?1", [album_id])?;
+    Ok(())
+}
+
+pub fn get_album_images(conn: &Connection, root: &Path, album_id: i64) -> Result<Vec<Image>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM album_images
+         JOIN images ON images.id = album_images.image_id
+         WHERE album_images.album_id = ?1
+         ORDER BY album_images.position",
+        IMAGE_COLUMNS
+    ))?;
+    stmt.query_map([album_id], |row| image_from_row(row, root))?
+        .collect()
+}
+
+pub fn insert_album_images(conn: &Connection, album_id: i64, image_ids: &[i64]) -> Result<()> {
+    let mut position: i64 = conn.query_row(
+        "SELECT COALESCE(MAX(position) + 1, 0) FROM album_images WHERE album_id = ?1",
+        [album_id],
+        |row| row.get(0),
+    )?;
+
+    for image_id in image_ids {
+        let added = conn.execute(
+            "INSERT OR IGNORE INTO album_images (album_id, image_id, position)
+             SELECT ?1, id, ?3 FROM images WHERE id = ?2",
+            params![album_id, image_id, position],
+        )?;
+        position += added as i64;
+    }
+
+    Ok(())
+}
+
+pub fn delete_album_images(conn: &Connection, album_id: i64, image_ids: &[i64]) -> Result<()> {
+    for image_id in image_ids {
+        conn.execute(
+            "DELETE FROM album_images WHERE album_id = ?1 AND image_id = ?2",
+            params![album_id, image_id],
+        )?;
+        conn.execute(
+            "UPDATE albums SET cover_image_id = NULL WHERE id = ?1 AND cover_image_id = ?2",
+            params![album_id, image_id],
+        )?;
+    }
+    Ok(())
+}
+
+pub fn reorder_album(conn: &Connection, album_id: i64, image_ids: &[i64]) -> Result<()> {
+    let mut current: Vec<i64> = conn
+        .prepare("SELECT image_id FROM album_images WHERE album_id = ?1 ORDER BY position")?
+        .query_map([album_id], |row| row.get(0))?
+        .collect::<Result<_>>()?;
+    current.retain(|id| !image_ids.contains(id));
+
+    let ordered = image_ids.iter().chain(current.iter());
+    for (position, image_id) in ordered.enumerate() {
+        conn.execute(
+            "UPDATE album_images SET position = ?1 WHERE album_id = ?2 AND image_id = ?3",
+            params![position as i64, album_id, image_id],
+        )?;
+    }
+
+    Ok(())
+}
+
+pub fn remove_image_from_albums(conn: &Connection, image_id: i64) -> Result<()> {
+    conn.execute("DELETE FROM album_images WHERE image_id = ?1", [image_id])?;
+    conn.execute(
+        "UPDATE albums SET cover_image_id = NULL WHERE cover_image_id = ?1",
+        [image_id],
+    )?;
+    Ok(())
+}
+
+pub fn get_album_folders(conn: &Connection) -> Result<Vec<AlbumFolder>> {
+    let mut stmt =
+        conn.prepare("SELECT id, name, parent_id FROM album_folders ORDER BY name COLLATE NOCASE")?;
+    stmt.query_map([], |row| {
+        Ok(AlbumFolder {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            parent_id: row.get(2)?,
+        })
+    })?
+    .collect()
+}
+
+pub fn album_folder_exists(conn: &Connection, folder_id: i64) -> Result<bool> {
+    conn.query_row(
+        "SELECT id FROM album_folders WHERE id = ?1",
+        [folder_id],
+        |row| row.get::<_, i64>(0),
+    )
+    .optional()
+    .map(|id| id.is_some())
+}
+
+pub fn get_folder_parent(conn: &Connection, folder_id: i64) -> Result<Option<i64>> {
+    conn.query_row(
+        "SELECT parent_id FROM album_folders WHERE id = ?1",
+        [folder_id],
+        |row| row.get(0),
+    )
+}
+
+pub fn insert_album_folder(conn: &Connection, name: &str, parent_id: Option<i64>) -> Result<i64> {
+    conn.execute(
+        "INSERT INTO album_folders (name, parent_id) VALUES (?1, ?2)",
+        params![name.trim(), parent_id],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+pub fn rename_album_folder(conn: &Connection, folder_id: i64, name: &str) -> Result<usize> {
+    conn.execute(
+        "UPDATE album_folders SET name = ?1 WHERE id = ?2",
+        params![name.trim(), folder_id],
+    )
+}
+
+pub fn set_folder_parent(
+    conn: &Connection,
+    folder_id: i64,
+    parent_id: Option<i64>,
+) -> Result<usize> {
+    conn.execute(
+        "UPDATE album_folders SET parent_id = ?1 WHERE id = ?2",
+        params![parent_id, folder_id],
+    )
+}
+
+pub fn get_folder_album_ids(conn: &Connection, folder_id: i64) -> Result<Vec<i64>> {
+    let mut stmt = conn.prepare("SELECT id FROM albums WHERE folder_id = ?1")?;
+    stmt.query_map([folder_id], |row| row.get(0))?.collect()
+}
+
+pub fn delete_album_folder(conn: &Connection, folder_id: i64) -> Result<()> {
+    let parent_id = get_folder_parent(conn, folder_id)?;
+
+    conn.execute(
+        "UPDATE album_folders SET parent_id = ?1 WHERE parent_id = ?2",
+        params![parent_id, folder_id],
+    )?;
+    conn.execute(
+        "UPDATE albums SET folder_id = ?1 WHERE folder_id = ?2",
+        params![parent_id, folder_id],
+    )?;
+    conn.execute("DELETE FROM album_folders WHERE id = ?1", [folder_id])?;
+
+    Ok(())
+}
+
+fn smart_album_from_row(conn: &Connection, row: &rusqlite::Row) -> Result<SmartAlbum> {
+    let query: String = row.get(2)?;
+    let image_count = match SearchQuery::parse(&query) {
+        Ok(search) => count_images(conn, &search)?,
+        Err(_) => 0,
+    };
+
+    Ok(SmartAlbum {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        query,
+        pinned: row.get(3)?,
+        image_count,
+        created_at: row.get(4)?,
+    })
+}
+
+pub fn get_smart_albums(conn: &Connection) -> Result<Vec<SmartAlbum>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, name, query, pinned, created_at FROM smart_albums
+         ORDER BY pinned DESC, name COLLATE NOCASE",
+    )?;
+    stmt.query_map([], |row| smart_album_from_row(conn, row))?
+        .collect()
+}
+
+pub fn get_smart_album(conn: &Connection, smart_album_id: i64) -> Result<SmartAlbum> {
+    conn.query_row(
+        "SELECT id, name, query, pinned, created_at FROM smart_albums WHERE id = ?1",
+        [smart_album_id],
+        |row| smart_album_from_row(conn, row),
+    )
+}
+
+pub fn insert_smart_album(conn: &Connection, album: &SmartAlbumInput) -> Result<i64> {
+    conn.execute(
+        "INSERT INTO smart_albums (name, query, pinned) VALUES (?1, ?2, ?3)",
+        params![album.name.trim(), album.query.trim(), album.pinned],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+pub fn update_smart_album(
+    conn: &Connection,
+    smart_album_id: i64,
+    album: &SmartAlbumInput,
+) -> Result<usize> {
+    conn.execute(
+        "UPDATE smart_albums SET name = ?1, query = ?2, pinned = ?3 WHERE id = ?4",
+        params![
+            album.name.trim(),
+            album.query.trim(),
+            album.pinned,
+            smart_album_id
+        ],
+    )
+}
+
+pub fn set_smart_album_pinned(
+    conn: &Connection,
+    smart_album_id: i64,
+    pinned: bool,
+) -> Result<usize> {
+    conn.execute(
+        "UPDATE smart_albums SET pinned = ?1 WHERE id = ?2",
+        params![pinned, smart_album_id],
+    )
+}
+
+pub fn restore_smart_album(conn: &Connection, album: &SmartAlbum) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO smart_albums (id, name, query, pinned, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            album.id,
+            album.name,
+            album.query,
+            album.pinned,
+            album.created_at
+        ],
+    )?;
+    Ok(())
+}
+
+pub fn delete_smart_album(conn: &Connection, smart_album_id: i64) -> Result<usize> {
+    conn.execute("DELETE FROM smart_albums WHERE id = ?1", [smart_album_id])
+}
@@ -0,0 +1,116 @@
+use crate::models::annotation::{Annotation, AnnotationInput, AnnotationKind};
+use rusqlite::{Connection, Result, params};
+
+const ANNOTATION_COLUMNS: &str = "id, image_id, kind, x1, y1, x2, y2, color, text, created_at";
+
+fn annotation_from_row(row: &rusqlite::Row) -> Result<Annotation> {
+    Ok(Annotation {
+        id: row.get(0)?,
+        image_id: row.get(1)?,
+        kind: AnnotationKind::parse(&row.get::<_, String>(2)?),
+        x1: row.get(3)?,
+        y1: row.get(4)?,
+        x2: row.get(5)?,
+        y2: row.get(6)?,
+        color: row.get(7)?,
+        text: row.get(8)?,
+        created_at: row.get(9)?,
+    })
+}
+
+pub fn get_annotations(conn: &Connection, image_id: i64) -> Result<Vec<Annotation>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM annotations WHERE image_id = ?1 ORDER BY id",
+        ANNOTATION_COLUMNS
+    ))?;
+    stmt.query_map([image_id], annotation_from_row)?.collect()
+}
+
+pub fn get_annotation(conn: &Connection, annotation_id: i64) -> Result<Annotation> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM annotations WHERE id = ?1",
+            ANNOTATION_COLUMNS
+        ),
+        [annotation_id],
+        annotation_from_row,
+    )
+}
+
+pub fn insert_annotation(
+    conn: &Connection,
+    image_id: i64,
+    annotation: &AnnotationInput,
+) -> Result<i64> {
+    conn.execute(
+        "INSERT INTO annotations (image_id, kind, x1, y1, x2, y2, color, text)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            image_id,
+            annotation.kind.as_str(),
+            annotation.x1,
+            annotation.y1,
+            annotation.x2,
+            annotation.y2,
+            annotation.color,
+            annotation.text.trim()
+        ],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+pub fn restore_annotation(conn: &Connection, annotation: &Annotation) -> Result<()> {
+    conn.execute(
+        &format!(
+            "INSERT OR REPLACE INTO annotations ({})
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            ANNOTATION_COLUMNS
+        ),
+        params![
+            annotation.id,
+            annotation.image_id,
+            annotation.kind.as_str(),
+            annotation.x1,
+            annotation.y1,
+            annotation.x2,
+            annotation.y2,
+            annotation.color,
+            annotation.text,
+            annotation.created_at
+        ],
+    )?;
+    Ok(())
+}
+
+pub fn update_annotation(
+    conn: &Connection,
+    annotation_id: i64,
+    annotation: &AnnotationInput,
+) -> Result<()> {
+    conn.execute(
+        "UPDATE annotations
+         SET kind = ?2, x1 = ?3, y1 = ?4, x2 = ?5, y2 = ?6, color = ?7, text = ?8
+         WHERE id = ?1",
+        params![
+            annotation_id,
+            annotation.kind.as_str(),
+            annotation.x1,
+            annotation.y1,
+            annotation.x2,
+            annotation.y2,
+            annotation.color,
+            annotation.text.trim()
+        ],
+    )?;
+    Ok(())
+}
+
+pub fn delete_annotation(conn: &Connection, annotation_id: i64) -> Result<()> {
+    conn.execute("DELETE FROM annotations WHERE id = ?1", [annotation_id])?;
+    Ok(())
+}
+
+pub fn delete_image_annotations(conn: &Connection, image_id: i64) -> Result<()> {
+    conn.execute("DELETE FROM annotations WHERE image_id = ?1", [image_id])?;
+    Ok(())
+}
@@ -0,0 +1,728 @@
+use crate::db::{albums, annotations, tags};
+use crate::models::image::{ColorLabel, Image, ImageMarks};
+use crate::models::search::{ImageSort, SearchQuery};
+use crate::models::settings::Settings;
+use crate::models::source::{ImageSource, SourceType};
+use crate::models::sync_folder::SyncFolder;
+use crate::utils::{path_utils, tag_utils};
+use r2d2::{Pool, PooledConnection};
+use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::{Connection, OpenFlags, OptionalExtension, Result, params, params_from_iter};
+use std::path::Path;
+use std::time::Duration;
+
+const READER_POOL_SIZE: u32 = 4;
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub const IMAGE_COLUMNS: &str = "images.id, images.filename, images.path, images.added_at,
+    images.rating, images.favorite, images.color_label";
+
+pub type DbConn = PooledConnection<SqliteConnectionManager>;
+
+pub struct Db {
+    writer: Pool<SqliteConnectionManager>,
+    readers: Pool<SqliteConnectionManager>,
+}
+
+impl Db {
+    pub fn open(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let writer = Pool::builder()
+            .max_size(1)
+            .build(connection_manager(path))?;
+        let reader_flags = OpenFlags::SQLITE_OPEN_READ_ONLY
+            | OpenFlags::SQLITE_OPEN_URI
+            | OpenFlags::SQLITE_OPEN_NO_MUTEX;
+        let readers = Pool::builder()
+            .max_size(READER_POOL_SIZE)
+            .build(connection_manager(path).with_flags(reader_flags))?;
+
+        Ok(Db { writer, readers })
+    }
+
+    pub fn write(&self) -> Result<DbConn, Box<dyn std::error::Error>> {
+        Ok(self.writer.get()?)
+    }
+
+    pub fn read(&self) -> Result<DbConn, Box<dyn std::error::Error>> {
+        Ok(self.readers.get()?)
+    }
+}
+
+pub const MIGRATIONS: &[&str] = &[
+    "CREATE TABLE IF NOT EXISTS images (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        filename TEXT NOT NULL,
+        path TEXT NOT NULL,
+        added_at DATETIME DEFAULT CURRENT_TIMESTAMP
+    )",
+    "CREATE VIRTUAL TABLE IF NOT EXISTS image_search USING fts5(
+        search_text
+    )",
+    "CREATE TABLE IF NOT EXISTS sync_folders (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        path TEXT NOT NULL UNIQUE,
+        move_images BOOLEAN DEFAULT FALSE
+    )",
+    "CREATE TABLE IF NOT EXISTS settings (
+        key TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+    )",
+    "ALTER TABLE images ADD COLUMN ocr_text TEXT NOT NULL DEFAULT ''",
+    "ALTER TABLE images ADD COLUMN content_hash TEXT;
+     CREATE INDEX IF NOT EXISTS idx_images_content_hash ON images(content_hash)",
+    "CREATE TABLE IF NOT EXISTS image_sources (
+        image_id INTEGER PRIMARY KEY,
+        source_type TEXT NOT NULL,
+        origin TEXT,
+        page_url TEXT,
+        last_modified TEXT,
+        etag TEXT,
+        batch_id TEXT,
+        imported_at DATETIME DEFAULT CURRENT_TIMESTAMP
+    )",
+    "CREATE TABLE IF NOT EXISTS album_folders (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        name TEXT NOT NULL,
+        parent_id INTEGER
+    );
+    CREATE TABLE IF NOT EXISTS albums (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        name TEXT NOT NULL,
+        description TEXT NOT NULL DEFAULT '',
+        cover_image_id INTEGER,
+        folder_id INTEGER,
+        created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+    );
+    CREATE TABLE IF NOT EXISTS album_images (
+        album_id INTEGER NOT NULL,
+        image_id INTEGER NOT NULL,
+        position INTEGER NOT NULL,
+        PRIMARY KEY (album_id, image_id)
+    );
+    CREATE INDEX IF NOT EXISTS idx_album_images_image ON album_images(image_id)",
+    "CREATE TABLE IF NOT EXISTS smart_albums (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        name TEXT NOT NULL,
+        query TEXT NOT NULL,
+        pinned BOOLEAN NOT NULL DEFAULT FALSE,
+        created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+    )",
+    "CREATE TABLE IF NOT EXISTS image_tags (
+        image_id INTEGER NOT NULL,
+        tag TEXT NOT NULL COLLATE NOCASE,
+        PRIMARY KEY (image_id, tag)
+    );
+    CREATE INDEX IF NOT EXISTS idx_image_tags_tag ON image_tags(tag);
+    CREATE TABLE IF NOT EXISTS tag_aliases (
+        alias TEXT PRIMARY KEY COLLATE NOCASE,
+        tag TEXT NOT NULL COLLATE NOCASE
+    );
+    CREATE TABLE IF NOT EXISTS tag_implications (
+        tag TEXT NOT NULL COLLATE NOCASE,
+        implied_tag TEXT NOT NULL COLLATE NOCASE,
+        PRIMARY KEY (tag, implied_tag)
+    );
+    WITH RECURSIVE split(image_id, word, rest) AS (
+        SELECT image_search.rowid, '', trim(replace(replace(
+            CASE WHEN images.ocr_text <> ''
+                AND substr(image_search.search_text, 1, length(images.ocr_text)) = images.ocr_text
+            THEN substr(image_search.search_text, length(images.ocr_text) + 1)
+            ELSE image_search.search_text END,
+            char(10), ' '), char(13), ' ')
+        ) || ' '
+        FROM image_search
+        JOIN images ON images.id = image_search.rowid
+        UNION ALL
+        SELECT image_id, substr(rest, 1, instr(rest, ' ') - 1), ltrim(substr(rest, instr(rest, ' ')))
+        FROM split
+        WHERE rest <> ''
+    )
+    INSERT OR IGNORE INTO image_tags (image_id, tag)
+    SELECT image_id, word FROM split WHERE word <> ''",
+    "ALTER TABLE image_tags ADD COLUMN added_at DATETIME;
+     UPDATE image_tags SET added_at = (
+        SELECT added_at FROM images WHERE images.id = image_tags.image_id
+     )",
+    "CREATE TABLE IF NOT EXISTS tag_rules (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        name TEXT NOT NULL,
+        enabled BOOLEAN NOT NULL DEFAULT TRUE,
+        ocr_pattern TEXT,
+        folder TEXT,
+        domain TEXT,
+        filename_pattern TEXT,
+        format TEXT,
+        min_width INTEGER,
+        max_width INTEGER,
+        min_height INTEGER,
+        max_height INTEGER,
+        tags TEXT NOT NULL,
+        created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+    )",
+    "CREATE TABLE IF NOT EXISTS operations (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        description TEXT NOT NULL,
+        payload TEXT NOT NULL,
+        undone BOOLEAN NOT NULL DEFAULT FALSE,
+        created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+    )",
+    "ALTER TABLE images ADD COLUMN notes TEXT NOT NULL DEFAULT ''",
+    "CREATE TABLE IF NOT EXISTS annotations (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        image_id INTEGER NOT NULL,
+        kind TEXT NOT NULL,
+        x1 REAL NOT NULL,
+        y1 REAL NOT NULL,
+        x2 REAL NOT NULL,
+        y2 REAL NOT NULL,
+        color TEXT NOT NULL,
+        text TEXT NOT NULL DEFAULT '',
+        created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+    );
+    CREATE INDEX IF NOT EXISTS idx_annotations_image_id ON annotations(image_id)",
+    "ALTER TABLE images ADD COLUMN rating INTEGER NOT NULL DEFAULT 0;
+     ALTER TABLE images ADD COLUMN favorite BOOLEAN NOT NULL DEFAULT FALSE;
+     ALTER TABLE images ADD COLUMN color_label TEXT",
+];
+
+pub fn open_database(path: &Path) -> Result<Connection, Box<dyn std::error::Error>> {
+    let conn = Connection::open(path)?;
+    conn.pragma_update(None, "journal_mode", "WAL")?;
+    run_migrations(&conn)?;
+    Ok(conn)
+}
+
+pub fn run_migrations(conn: &Connection) -> Result<(), Box<dyn std::error::Error>> {
+    let version: usize = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute_batch(migration)?;
+        tx.pragma_update(None, "user_version", index + 1)?;
+        tx.commit()?;
+    }
+
+    Ok(())
+}
+
+fn connection_manager(path: &Path) -> SqliteConnectionManager {
+    SqliteConnectionManager::file(path).with_init(|conn| {
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        conn.pragma_update(None, "synchronous", "NORMAL")
+    })
+}
+
+fn sanitize_fts_token(token: &str) -> String {
+    token
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == '_' || *c == '-')
+        .collect()
+}
+
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+pub fn image_from_row(row: &rusqlite::Row, root: &Path) -> Result<Image> {
+    Ok(Image {
+        id: row.get(0)?,
+        filename: row.get(1)?,
+        path: path_utils::resolve_stored_path(root, &row.get::<_, String>(2)?),
+        added_at: row.get(3)?,
+        marks: ImageMarks {
+            rating: row.get(4)?,
+            favorite: row.get(5)?,
+            color_label: row
+                .get::<_, Option<String>>(6)?
+                .as_deref()
+                .and_then(ColorLabel::parse),
+        },
+    })
+}
+
+pub fn insert_image(
+    conn: &Connection,
+    root: &Path,
+    filename: &str,
+    full_path: &str,
+    ocr_text: &str,
+    content_hash: Option<&str>,
+) -> Result<i64, Box<dyn std::error::Error>> {
+    conn.execute(
+        "INSERT INTO images (filename, path, ocr_text, content_hash) VALUES (?1, ?2, ?3, ?4)",
+        params![
+            filename,
+            path_utils::to_stored_path(root, Path::new(full_path)),
+            ocr_text,
+            content_hash
+        ],
+    )?;
+
+    let image_id = conn.last_insert_rowid();
+
+    conn.execute(
+        "INSERT INTO image_search (rowid, search_text) VALUES (?1, ?2)",
+        params![image_id, ocr_text],
+    )?;
+
+    Ok(image_id)
+}
+
+pub fn image_exists(conn: &Connection, filename: &str) -> Result<bool> {
+    conn.query_row(
+        "SELECT id FROM images WHERE filename = ?1",
+        params![filename],
+        |row| row.get::<_, i64>(0),
+    )
+    .optional()
+    .map(|id| id.is_some())
+}
+
+pub fn find_image_by_hash(conn: &Connection, root: &Path, hash: &str) -> Result<Option<Image>> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM images WHERE content_hash = ?1",
+            IMAGE_COLUMNS
+        ),
+        [hash],
+        |row| image_from_row(row, root),
+    )
+    .optional()
+}
+
+pub fn get_unhashed_images(conn: &Connection, root: &Path) -> Result<Vec<Image>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM images WHERE content_hash IS NULL",
+        IMAGE_COLUMNS
+    ))?;
+    stmt.query_map([], |row| image_from_row(row, root))?
+        .collect()
+}
+
+pub fn set_content_hash(conn: &Connection, image_id: i64, hash: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE images SET content_hash = ?1 WHERE id = ?2",
+        params![hash, image_id],
+    )?;
+    Ok(())
+}
+
+pub fn get_image(conn: &Connection, root: &Path, image_id: i64) -> Result<Image> {
+    conn.query_row(
+        &format!("SELECT {} FROM images WHERE images.id = ?1", IMAGE_COLUMNS),
+        [image_id],
+        |row| image_from_row(row, root),
+    )
+}
+
+pub fn get_images(conn: &Connection, root: &Path) -> Result<Vec<Image>> {
+    get_sorted_images(conn, root, &ImageSort::default())
+}
+
+pub fn get_sorted_images(conn: &Connection, root: &Path, sort: &ImageSort) -> Result<Vec<Image>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM images ORDER BY {}",
+        IMAGE_COLUMNS,
+        sort.order_by()
+    ))?;
+    stmt.query_map([], |row| image_from_row(row, root))?
+        .collect()
+}
+
+fn search_filter(query: &SearchQuery) -> (String, Vec<String>) {
+    let fts = query
+        .terms
+        .iter()
+        .flat_map(|t| t.split(|c: char| !c.is_alphanumeric() && c != '_' && c != '-'))
+        .map(|t| {
+            let t = sanitize_fts_token(t);
+            if t.is_empty() { t } else { format!("{}*", t) }
+        })
+        .filter(|t| !t.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut conditions = Vec::new();
+    let mut values = Vec::new();
+
+    for source in &query.sources {
+        values.push(format!("%{}%", escape_like(source)));
+        values.push(source.to_lowercase());
+        conditions.push(format!(
+            "EXISTS (SELECT 1 FROM image_sources
+                 WHERE image_sources.image_id = images.id
+                 AND (image_sources.origin LIKE ?{0} ESCAPE '\\'
+                      OR image_sources.page_url LIKE ?{0} ESCAPE '\\'
+                      OR image_sources.source_type = ?{1}))",
+            values.len() - 1,
+            values.len()
+        ));
+    }
+
+    for tag in &query.tags {
+        values.push(tag_utils::normalize_tag(tag));
+        conditions.push(format!(
+            "EXISTS (SELECT 1 FROM image_tags,
+                 (SELECT COALESCE((SELECT tag FROM tag_aliases WHERE alias = ?{0}), ?{0}) AS name)
+                     AS wanted
+                 WHERE image_tags.image_id = images.id
+                 AND (image_tags.tag = wanted.name
+                      OR lower(substr(image_tags.tag, 1, length(wanted.name) + 1))
+                          IN (lower(wanted.name) || '/', lower(wanted.name) || ':')))",
+            values.len()
+        ));
+    }
+
+    for text in &query.text {
+        values.push(format!("%{}%", escape_like(text)));
+        conditions.push(format!(
+            "images.ocr_text LIKE ?{} ESCAPE '\\'",
+            values.len()
+        ));
+    }
+
+    if !query.formats.is_empty() {
+        let formats = query
+            .formats
+            .iter()
+            .flat_map(|format| match format.as_str() {
+                "jpg" | "jpeg" => vec!["jpg", "jpeg"],
+                "tif" | "tiff" => vec!["tif", "tiff"],
+                format => vec![format],
+            })
+            .map(|format| {
+                values.push(format!("%.{}", escape_like(format)));
+                format!("images.filename LIKE ?{} ESCAPE '\\'", values.len())
+            })
+            .collect::<Vec<_>>();
+        conditions.push(format!("({})", formats.join(" OR ")));
+    }
+
+    if let Some(after) = query.after {
+        values.push(after.to_string());
+        conditions.push(format!("date(images.added_at) >= ?{}", values.len()));
+    }
+
+    if let Some(before) = query.before {
+        values.push(before.to_string());
+        conditions.push(format!("date(images.added_at) < ?{}", values.len()));
+    }
+
+    if query.favorite {
+        conditions.push("images.favorite = TRUE".to_string());
+    }
+    if let Some(min_rating) = query.min_rating {
+        values.push(min_rating.to_string());
+        conditions.push(format!("images.rating >= ?{}", values.len()));
+    }
+    if let Some(max_rating) = query.max_rating {
+        values.push(max_rating.to_string());
+        conditions.push(format!("images.rating <= ?{}", values.len()));
+    }
+
+    if !query.labels.is_empty() {
+        let labels = query
+            .labels
+            .iter()
+            .map(|label| match label {
+                Some(label) => {
+                    values.push(label.as_str().to_string());
+                    format!("images.color_label = ?{}", values.len())
+                }
+                None => "images.color_label IS NULL".to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(" OR ");
+        conditions.push(format!("({})", labels));
+    }
+
+    if !fts.is_empty() {
+        values.push(fts);
+        conditions.push(format!(
+            "images.id IN (SELECT rowid FROM image_search WHERE image_search MATCH ?{})",
+            values.len()
+        ));
+    }
+    if conditions.is_empty() {
+        conditions.push("TRUE".to_string());
+    }
+
+    (conditions.join(" AND "), values)
+}
+
+pub fn search_images(conn: &Connection, root: &Path, query: &SearchQuery) -> Result<Vec<Image>> {
+    let (filter, values) = search_filter(query);
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM images WHERE {} ORDER BY {}",
+        IMAGE_COLUMNS,
+        filter,
+        query.sort.order_by()
+    ))?;
+
+    stmt.query_map(params_from_iter(values), |row| image_from_row(row, root))?
+        .collect()
+}
+
+pub fn count_images(conn: &Connection, query: &SearchQuery) -> Result<usize> {
+    let (filter, values) = search_filter(query);
+    conn.query_row(
+        &format!("SELECT COUNT(*) FROM images WHERE {}", filter),
+        params_from_iter(values),
+        |row| row.get::<_, i64>(0),
+    )
+    .map(|count| count as usize)
+}
+
+pub fn get_tags(conn: &Connection, image_id: i64) -> Result<Vec<String>> {
+    let mut stmt =
+        conn.prepare("SELECT tag FROM image_tags WHERE image_id = ?1 ORDER BY tag COLLATE NOCASE")?;
+    stmt.query_map([image_id], |row| row.get(0))?.collect()
+}
+
+pub fn refresh_search_text(conn: &Connection, image_id: i64) -> Result<()> {
+    conn.execute("DELETE FROM image_search WHERE rowid = ?1", [image_id])?;
+    conn.execute(
+        "INSERT INTO image_search (rowid, search_text)
+         SELECT id, trim(ocr_text || ' ' || notes || ' ' || COALESCE(
+             (SELECT group_concat(tag, ' ') FROM image_tags WHERE image_id = images.id), ''
+         ))
+         FROM images WHERE id = ?1",
+        [image_id],
+    )?;
+
+    Ok(())
+}
+
+pub fn add_tag(
+    conn: &mut Connection,
+    image_id: i64,
+    new_tag: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tx = conn.transaction()?;
+    insert_tag(&tx, image_id, new_tag)?;
+    tx.commit()?;
+
+    Ok(())
+}
+
+pub fn insert_tag(conn: &Connection, image_id: i64, new_tag: &str) -> Result<()> {
+    let tag = tag_utils::normalize_tag(new_tag);
+    if tag.is_empty() {
+        return Ok(());
+    }
+
+    for tag in tags::expand_tag(conn, &tag)? {
+        conn.execute(
+            "INSERT OR IGNORE INTO image_tags (image_id, tag, added_at)
+             SELECT id, ?2, CURRENT_TIMESTAMP FROM images WHERE id = ?1",
+            params![image_id, tag],
+        )?;
+    }
+    refresh_search_text(conn, image_id)
+}
+
+pub fn remove_tag(conn: &Connection, image_id: i64, tag: &str) -> Result<()> {
+    let tag = tag_utils::normalize_tag(tag);
+    if tag.is_empty() {
+        return Ok(());
+    }
+
+    conn.execute(
+        "DELETE FROM image_tags WHERE image_id = ?1 AND tag = ?2",
+        params![image_id, tags::resolve_alias(conn, &tag)?],
+    )?;
+    refresh_search_text(conn, image_id)
+}
+
+pub fn set_ocr_text(conn: &Connection, image_id: i64, text: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE images SET ocr_text = ?1 WHERE id = ?2",
+        (text, image_id),
+    )?;
+    refresh_search_text(conn, image_id)
+}
+
+pub fn delete_image(conn: &Connection, image_id: i64) -> Result<()> {
+    conn.execute("DELETE FROM images WHERE id = ?1", [image_id])?;
+    conn.execute("DELETE FROM image_search WHERE rowid = ?1", [image_id])?;
+    conn.execute("DELETE FROM image_tags WHERE image_id = ?1", [image_id])?;
+    conn.execute("DELETE FROM image_sources WHERE image_id = ?1", [image_id])?;
+    albums::remove_image_from_albums(conn, image_id)?;
+    annotations::delete_image_annotations(conn, image_id)?;
+
+    Ok(())
+}
+
+pub fn insert_source(conn: &Connection, image_id: i64, source: &ImageSource) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO image_sources
+         (image_id, source_type, origin, page_url, last_modified, etag, batch_id)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            image_id,
+            source.source_type.as_str(),
+            source.origin,
+            source.page_url,
+            source.last_modified,
+            source.etag,
+            source.batch_id
+        ],
+    )?;
+    Ok(())
+}
+
+pub fn get_source(conn: &Connection, image_id: i64) -> Result<Option<ImageSource>> {
+    conn.query_row(
+        "SELECT source_type, origin, page_url, last_modified, etag, batch_id, imported_at
+         FROM image_sources WHERE image_id = ?1",
+        [image_id],
+        |row| {
+            Ok(ImageSource {
+                source_type: SourceType::parse(&row.get::<_, String>(0)?),
+                origin: row.get(1)?,
+                page_url: row.get(2)?,
+                last_modified: row.get(3)?,
+                etag: row.get(4)?,
+                batch_id: row.get(5)?,
+                imported_at: row.get(6)?,
+            })
+        },
+    )
+    .optional()
+}
+
+pub fn get_ocr_text(conn: &Connection, image_id: i64) -> Result<String> {
+    conn.query_row(
+        "SELECT ocr_text FROM images WHERE id = ?1",
+        [image_id],
+        |row| row.get(0),
+    )
+}
+
+pub fn get_marks(conn: &Connection, image_id: i64) -> Result<ImageMarks> {
+    conn.query_row(
+        "SELECT rating, favorite, color_label FROM images WHERE id = ?1",
+        [image_id],
+        |row| {
+            Ok(ImageMarks {
+                rating: row.get(0)?,
+                favorite: row.get(1)?,
+                color_label: row
+                    .get::<_, Option<String>>(2)?
+                    .as_deref()
+                    .and_then(ColorLabel::parse),
+            })
+        },
+    )
+}
+
+pub fn set_marks(conn: &Connection, image_id: i64, marks: &ImageMarks) -> Result<()> {
+    conn.execute(
+        "UPDATE images SET rating = ?1, favorite = ?2, color_label = ?3 WHERE id = ?4",
+        params![
+            marks.rating,
+            marks.favorite,
+            marks.color_label.map(|label| label.as_str()),
+            image_id
+        ],
+    )?;
+    Ok(())
+}
+
+pub fn get_notes(conn: &Connection, image_id: i64) -> Result<String> {
+    conn.query_row(
+        "SELECT notes FROM images WHERE id = ?1",
+        [image_id],
+        |row| row.get(0),
+    )
+}
+
+pub fn set_notes(conn: &Connection, image_id: i64, notes: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE images SET notes = ?1 WHERE id = ?2",
+        (notes, image_id),
+    )?;
+    refresh_search_text(conn, image_id)
+}
+
+pub fn relativize_paths(conn: &Connection, root: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let mut stmt = conn.prepare("SELECT id, path FROM images")?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<Result<Vec<_>>>()?;
+
+    for (id, path) in rows {
+        if let Ok(relative) = Path::new(&path).strip_prefix(root) {
+            conn.execute(
+                "UPDATE images SET path = ?1 WHERE id = ?2",
+                params![relative.to_string_lossy(), id],
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn get_settings(conn: &Connection) -> Result<Settings, Box<dyn std::error::Error>> {
+    let mut stmt = conn.prepare("SELECT key, value FROM settings")?;
+    let values = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut map = serde_json::Map::new();
+    for (key, value) in values {
+        map.insert(key, serde_json::from_str(&value)?);
+    }
+
+    Ok(serde_json::from_value(serde_json::Value::Object(map))?)
+}
+
+pub fn save_settings(
+    conn: &Connection,
+    settings: &Settings,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let serde_json::Value::Object(map) = serde_json::to_value(settings)? else {
+        return Err("Invalid settings".into());
+    };
+
+    for (key, value) in map {
+        conn.execute(
+            "INSERT INTO settings (key, value) VALUES (?1, ?2)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![key, value.to_string()],
+        )?;
+    }
+
+    Ok(())
+}
+
+pub fn add_sync_folder(
+    conn: &Connection,
+    path: String,
+    move_images: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    conn.execute(
+        "INSERT INTO sync_folders (path, move_images) VALUES (?1, ?2)",
+        params![path, move_images],
+    )?;
+    Ok(())
+}
+
+pub fn get_sync_folders(conn: &Connection) -> Result<Vec<SyncFolder>> {
+    let mut stmt = conn.prepare("SELECT id, path, move_images FROM sync_folders")?;
+    stmt.query_map([], |row| {
+        Ok(SyncFolder {
+            id: row.get(0)?,
+            path: row.get(1)?,
+            move_images: row.get(2)?,
+        })
+    })?
+    .collect()
+}
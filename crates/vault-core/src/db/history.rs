@@ -0,0 +1,590 @@
+use crate::db::{albums, annotations, database, tags};
+use crate::models::album::{AlbumFolder, SmartAlbum};
+use crate::models::annotation::Annotation;
+use crate::models::history::{
+    AlbumChange, AlbumPosition, AlbumRecord, AnnotationChange, DeletedImage, HistoryEntry,
+    MarksChange, NotesChange, OcrChange, Operation, SmartAlbumChange, TagChange, TagRuleChange,
+    TagRulesState,
+};
+use crate::models::image::ImageMarks;
+use crate::models::tag_rule::TagRule;
+use rusqlite::{Connection, OptionalExtension, Result, params};
+
+const MAX_HISTORY: i64 = 500;
+
+fn to_sql_error(e: serde_json::Error) -> rusqlite::Error {
+    rusqlite::Error::ToSqlConversionFailure(Box::new(e))
+}
+
+pub fn record(conn: &Connection, description: &str, operation: &Operation) -> Result<()> {
+    let payload = serde_json::to_string(operation).map_err(to_sql_error)?;
+
+    conn.execute("DELETE FROM operations WHERE undone = TRUE", [])?;
+    conn.execute(
+        "INSERT INTO operations (description, payload) VALUES (?1, ?2)",
+        params![description, payload],
+    )?;
+    conn.execute(
+        "DELETE FROM operations WHERE id <= (SELECT MAX(id) FROM operations) - ?1",
+        [MAX_HISTORY],
+    )?;
+
+    Ok(())
+}
+
+pub fn get_history(conn: &Connection, limit: usize) -> Result<Vec<HistoryEntry>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, description, undone, created_at FROM operations ORDER BY id DESC LIMIT ?1",
+    )?;
+    stmt.query_map([limit as i64], |row| {
+        Ok(HistoryEntry {
+            id: row.get(0)?,
+            description: row.get(1)?,
+            undone: row.get(2)?,
+            created_at: row.get(3)?,
+        })
+    })?
+    .collect()
+}
+
+fn operation_from_row(row: &rusqlite::Row) -> Result<(HistoryEntry, Operation)> {
+    let payload: String = row.get(4)?;
+    let operation = serde_json::from_str(&payload).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(4, rusqlite::types::Type::Text, Box::new(e))
+    })?;
+
+    Ok((
+        HistoryEntry {
+            id: row.get(0)?,
+            description: row.get(1)?,
+            undone: row.get(2)?,
+            created_at: row.get(3)?,
+        },
+        operation,
+    ))
+}
+
+pub fn next_undo(conn: &Connection) -> Result<Option<(HistoryEntry, Operation)>> {
+    conn.query_row(
+        "SELECT id, description, undone, created_at, payload FROM operations
+         WHERE undone = FALSE ORDER BY id DESC LIMIT 1",
+        [],
+        operation_from_row,
+    )
+    .optional()
+}
+
+pub fn next_redo(conn: &Connection) -> Result<Option<(HistoryEntry, Operation)>> {
+    conn.query_row(
+        "SELECT id, description, undone, created_at, payload FROM operations
+         WHERE undone = TRUE ORDER BY id LIMIT 1",
+        [],
+        operation_from_row,
+    )
+    .optional()
+}
+
+pub fn set_undone(conn: &Connection, operation_id: i64, undone: bool) -> Result<usize> {
+    conn.execute(
+        "UPDATE operations SET undone = ?1 WHERE id = ?2",
+        params![undone, operation_id],
+    )
+}
+
+pub fn get_trash_files(conn: &Connection) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare("SELECT payload FROM operations WHERE undone = FALSE")?;
+    let payloads = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut stmt = conn.prepare("SELECT id || '-' || filename FROM images")?;
+    let live = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(payloads
+        .iter()
+        .filter_map(|payload| serde_json::from_str::<Operation>(payload).ok())
+        .flat_map(|operation| match operation {
+            Operation::DeleteImages { images } => {
+                images.into_iter().map(|image| image.trash_file).collect()
+            }
+            _ => Vec::new(),
+        })
+        .chain(live)
+        .collect())
+}
+
+pub fn tag_states(conn: &Connection, image_ids: &[i64]) -> Result<Vec<(i64, Vec<String>)>> {
+    image_ids
+        .iter()
+        .map(|&image_id| Ok((image_id, database::get_tags(conn, image_id)?)))
+        .collect()
+}
+
+pub fn record_tags(
+    conn: &Connection,
+    description: &str,
+    before: Vec<(i64, Vec<String>)>,
+    rules_before: Option<TagRulesState>,
+) -> Result<()> {
+    let changes = before
+        .into_iter()
+        .map(|(image_id, before)| {
+            Ok(TagChange {
+                image_id,
+                before,
+                after: database::get_tags(conn, image_id)?,
+            })
+        })
+        .filter(|change| !matches!(change, Ok(change) if change.before == change.after))
+        .collect::<Result<Vec<_>>>()?;
+
+    let rules = match rules_before {
+        Some(before) => {
+            Some((before, rules_state(conn)?)).filter(|(before, after)| before != after)
+        }
+        None => None,
+    };
+
+    if changes.is_empty() && rules.is_none() {
+        return Ok(());
+    }
+    record(conn, description, &Operation::Tags { changes, rules })
+}
+
+pub fn set_image_tags(conn: &Connection, image_id: i64, tags: &[String]) -> Result<()> {
+    conn.execute("DELETE FROM image_tags WHERE image_id = ?1", [image_id])?;
+    for tag in tags {
+        conn.execute(
+            "INSERT OR IGNORE INTO image_tags (image_id, tag, added_at)
+             SELECT id, ?2, CURRENT_TIMESTAMP FROM images WHERE id = ?1",
+            params![image_id, tag],
+        )?;
+    }
+    database::refresh_search_text(conn, image_id)
+}
+
+pub fn mark_states(conn: &Connection, image_ids: &[i64]) -> Result<Vec<(i64, ImageMarks)>> {
+    image_ids
+        .iter()
+        .map(|&image_id| Ok((image_id, database::get_marks(conn, image_id)?)))
+        .collect()
+}
+
+pub fn record_marks(
+    conn: &Connection,
+    description: &str,
+    before: Vec<(i64, ImageMarks)>,
+) -> Result<()> {
+    let changes = before
+        .into_iter()
+        .map(|(image_id, before)| {
+            Ok(MarksChange {
+                image_id,
+                before,
+                after: database::get_marks(conn, image_id)?,
+            })
+        })
+        .filter(|change| !matches!(change, Ok(change) if change.before == change.after))
+        .collect::<Result<Vec<_>>>()?;
+
+    if changes.is_empty() {
+        return Ok(());
+    }
+    record(conn, description, &Operation::Marks { changes })
+}
+
+pub fn record_notes(
+    conn: &Connection,
+    description: &str,
+    image_id: i64,
+    before: String,
+) -> Result<()> {
+    let after = database::get_notes(conn, image_id)?;
+    if before == after {
+        return Ok(());
+    }
+
+    let changes = vec![NotesChange {
+        image_id,
+        before,
+        after,
+    }];
+    record(conn, description, &Operation::Notes { changes })
+}
+
+pub fn annotation_state(conn: &Connection, annotation_id: i64) -> Result<Option<Annotation>> {
+    annotations::get_annotation(conn, annotation_id).optional()
+}
+
+pub fn set_annotation(
+    conn: &Connection,
+    annotation_id: i64,
+    annotation: Option<&Annotation>,
+) -> Result<()> {
+    match annotation {
+        Some(annotation) => annotations::restore_annotation(conn, annotation),
+        None => annotations::delete_annotation(conn, annotation_id),
+    }
+}
+
+pub fn record_annotation(
+    conn: &Connection,
+    description: &str,
+    annotation_id: i64,
+    before: Option<Annotation>,
+) -> Result<()> {
+    let after = annotation_state(conn, annotation_id)?;
+    if before == after {
+        return Ok(());
+    }
+
+    let changes = vec![AnnotationChange {
+        annotation_id,
+        before,
+        after,
+    }];
+    record(conn, description, &Operation::Annotations { changes })
+}
+
+pub fn ocr_states(conn: &Connection, image_ids: &[i64]) -> Result<Vec<(i64, String)>> {
+    image_ids
+        .iter()
+        .map(|&image_id| Ok((image_id, database::get_ocr_text(conn, image_id)?)))
+        .collect()
+}
+
+pub fn record_ocr(conn: &Connection, description: &str, before: Vec<(i64, String)>) -> Result<()> {
+    let changes = before
+        .into_iter()
+        .map(|(image_id, before)| {
+            Ok(OcrChange {
+                image_id,
+                before,
+                after: database::get_ocr_text(conn, image_id)?,
+            })
+        })
+        .filter(|change| !matches!(change, Ok(change) if change.before == change.after))
+        .collect::<Result<Vec<_>>>()?;
+
+    if changes.is_empty() {
+        return Ok(());
+    }
+    record(conn, description, &Operation::Ocr { changes })
+}
+
+pub fn tag_rule_state(conn: &Connection, rule_id: i64) -> Result<Option<TagRule>> {
+    tags::get_rule(conn, rule_id).optional()
+}
+
+pub fn set_tag_rule(conn: &Connection, rule_id: i64, rule: Option<&TagRule>) -> Result<()> {
+    match rule {
+        Some(rule) => tags::restore_rule(conn, rule),
+        None => tags::delete_rule(conn, rule_id).map(|_| ()),
+    }
+}
+
+pub fn record_tag_rule(
+    conn: &Connection,
+    description: &str,
+    rule_id: i64,
+    before: Option<TagRule>,
+) -> Result<()> {
+    let after = tag_rule_state(conn, rule_id)?;
+    if before == after {
+        return Ok(());
+    }
+
+    let changes = vec![TagRuleChange {
+        rule_id,
+        before,
+        after,
+    }];
+    record(conn, description, &Operation::TagRules { changes })
+}
+
+pub fn smart_album_state(conn: &Connection, smart_album_id: i64) -> Result<Option<SmartAlbum>> {
+    albums::get_smart_album(conn, smart_album_id).optional()
+}
+
+pub fn set_smart_album(
+    conn: &Connection,
+    smart_album_id: i64,
+    album: Option<&SmartAlbum>,
+) -> Result<()> {
+    match album {
+        Some(album) => albums::restore_smart_album(conn, album),
+        None => albums::delete_smart_album(conn, smart_album_id).map(|_| ()),
+    }
+}
+
+pub fn record_smart_album(
+    conn: &Connection,
+    description: &str,
+    smart_album_id: i64,
+    before: Option<SmartAlbum>,
+) -> Result<()> {
+    let after = smart_album_state(conn, smart_album_id)?;
+    if before == after {
+        return Ok(());
+    }
+
+    let changes = vec![SmartAlbumChange {
+        smart_album_id,
+        before,
+        after,
+    }];
+    record(conn, description, &Operation::SmartAlbums { changes })
+}
+
+pub fn rules_state(conn: &Connection) -> Result<TagRulesState> {
+    Ok(TagRulesState {
+        aliases: tags::get_aliases(conn)?,
+        implications: tags::get_implications(conn)?,
+    })
+}
+
+pub fn set_rules_state(conn: &Connection, state: &TagRulesState) -> Result<()> {
+    conn.execute("DELETE FROM tag_aliases", [])?;
+    conn.execute("DELETE FROM tag_implications", [])?;
+    for alias in &state.aliases {
+        conn.execute(
+            "INSERT OR REPLACE INTO tag_aliases (alias, tag) VALUES (?1, ?2)",
+            params![alias.alias, alias.tag],
+        )?;
+    }
+    for implication in &state.implications {
+        conn.execute(
+            "INSERT OR IGNORE INTO tag_implications (tag, implied_tag) VALUES (?1, ?2)",
+            params![implication.tag, implication.implied_tag],
+        )?;
+    }
+    Ok(())
+}
+
+pub fn album_image_ids(conn: &Connection, album_id: i64) -> Result<Vec<i64>> {
+    let mut stmt =
+        conn.prepare("SELECT image_id FROM album_images WHERE album_id = ?1 ORDER BY position")?;
+    stmt.query_map([album_id], |row| row.get(0))?.collect()
+}
+
+pub fn set_album_images(conn: &Connection, album_id: i64, image_ids: &[i64]) -> Result<()> {
+    conn.execute("DELETE FROM album_images WHERE album_id = ?1", [album_id])?;
+    albums::insert_album_images(conn, album_id, image_ids)
+}
+
+pub fn album_record(conn: &Connection, album_id: i64) -> Result<Option<AlbumRecord>> {
+    let record = conn
+        .query_row(
+            "SELECT id, name, description, cover_image_id, folder_id, created_at
+             FROM albums WHERE id = ?1",
+            [album_id],
+            |row| {
+                Ok(AlbumRecord {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    description: row.get(2)?,
+                    cover_image_id: row.get(3)?,
+                    folder_id: row.get(4)?,
+                    created_at: row.get(5)?,
+                    image_ids: Vec::new(),
+                })
+            },
+        )
+        .optional()?;
+
+    record
+        .map(|record| {
+            Ok(AlbumRecord {
+                image_ids: album_image_ids(conn, album_id)?,
+                ..record
+            })
+        })
+        .transpose()
+}
+
+pub fn set_album(conn: &Connection, album_id: i64, record: Option<&AlbumRecord>) -> Result<()> {
+    let Some(record) = record else {
+        return albums::delete_album(conn, album_id);
+    };
+
+    conn.execute(
+        "INSERT OR REPLACE INTO albums (id, name, description, cover_image_id, folder_id, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            record.id,
+            record.name,
+            record.description,
+            record.cover_image_id,
+            record.folder_id,
+            record.created_at
+        ],
+    )?;
+    set_album_images(conn, album_id, &record.image_ids)
+}
+
+pub fn album_states(
+    conn: &Connection,
+    album_ids: &[i64],
+) -> Result<Vec<(i64, Option<AlbumRecord>)>> {
+    album_ids
+        .iter()
+        .map(|&album_id| Ok((album_id, album_record(conn, album_id)?)))
+        .collect()
+}
+
+pub fn record_albums(
+    conn: &Connection,
+    description: &str,
+    before: Vec<(i64, Option<AlbumRecord>)>,
+) -> Result<()> {
+    let changes = before
+        .into_iter()
+        .map(|(album_id, before)| {
+            Ok(AlbumChange {
+                album_id,
+                before,
+                after: album_record(conn, album_id)?,
+            })
+        })
+        .filter(|change| !matches!(change, Ok(change) if change.before == change.after))
+        .collect::<Result<Vec<_>>>()?;
+
+    if changes.is_empty() {
+        return Ok(());
+    }
+    record(conn, description, &Operation::Albums { changes })
+}
+
+pub fn set_album_folders(conn: &Connection, folders: &[AlbumFolder]) -> Result<()> {
+    conn.execute("DELETE FROM album_folders", [])?;
+    for folder in folders {
+        conn.execute(
+            "INSERT INTO album_folders (id, name, parent_id) VALUES (?1, ?2, ?3)",
+            params![folder.id, folder.name, folder.parent_id],
+        )?;
+    }
+    Ok(())
+}
+
+pub fn record_album_folders(
+    conn: &Connection,
+    description: &str,
+    folders_before: Vec<AlbumFolder>,
+    albums_before: Vec<(i64, Option<AlbumRecord>)>,
+) -> Result<()> {
+    let albums = albums_before
+        .into_iter()
+        .map(|(album_id, before)| {
+            Ok(AlbumChange {
+                album_id,
+                before,
+                after: album_record(conn, album_id)?,
+            })
+        })
+        .filter(|change| !matches!(change, Ok(change) if change.before == change.after))
+        .collect::<Result<Vec<_>>>()?;
+
+    let folders_after = albums::get_album_folders(conn)?;
+    if folders_before == folders_after && albums.is_empty() {
+        return Ok(());
+    }
+    record(
+        conn,
+        description,
+        &Operation::AlbumFolders {
+            folders: (folders_before, folders_after),
+            albums,
+        },
+    )
+}
+
+pub fn deleted_image(conn: &Connection, image_id: i64, trash_file: String) -> Result<DeletedImage> {
+    let (filename, path, added_at, ocr_text, content_hash) = conn.query_row(
+        "SELECT filename, path, added_at, ocr_text, content_hash FROM images WHERE id = ?1",
+        [image_id],
+        |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?,
+            ))
+        },
+    )?;
+
+    let mut stmt = conn.prepare(
+        "SELECT album_images.album_id, album_images.position,
+             albums.cover_image_id IS NOT NULL AND albums.cover_image_id = album_images.image_id
+         FROM album_images
+         JOIN albums ON albums.id = album_images.album_id
+         WHERE album_images.image_id = ?1",
+    )?;
+    let albums = stmt
+        .query_map([image_id], |row| {
+            Ok(AlbumPosition {
+                album_id: row.get(0)?,
+                position: row.get(1)?,
+                cover: row.get(2)?,
+            })
+        })?
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(DeletedImage {
+        id: image_id,
+        filename,
+        path,
+        added_at,
+        ocr_text,
+        content_hash,
+        trash_file,
+        tags: database::get_tags(conn, image_id)?,
+        source: database::get_source(conn, image_id)?,
+        albums,
+        notes: database::get_notes(conn, image_id)?,
+        annotations: annotations::get_annotations(conn, image_id)?,
+        marks: database::get_marks(conn, image_id)?,
+    })
+}
+
+pub fn restore_image(conn: &Connection, image: &DeletedImage) -> Result<()> {
+    conn.execute(
+        "INSERT INTO images (id, filename, path, added_at, ocr_text, content_hash)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            image.id,
+            image.filename,
+            image.path,
+            image.added_at,
+            image.ocr_text,
+            image.content_hash
+        ],
+    )?;
+
+    if let Some(source) = &image.source {
+        database::insert_source(conn, image.id, source)?;
+    }
+    for album in &image.albums {
+        conn.execute(
+            "INSERT OR IGNORE INTO album_images (album_id, image_id, position)
+             SELECT id, ?2, ?3 FROM albums WHERE id = ?1",
+            params![album.album_id, image.id, album.position],
+        )?;
+        if album.cover {
+            conn.execute(
+                "UPDATE albums SET cover_image_id = ?2 WHERE id = ?1",
+                params![album.album_id, image.id],
+            )?;
+        }
+    }
+
+    for annotation in &image.annotations {
+        annotations::restore_annotation(conn, annotation)?;
+    }
+
+    database::set_marks(conn, image.id, &image.marks)?;
+    database::set_notes(conn, image.id, &image.notes)?;
+    set_image_tags(conn, image.id, &image.tags)
+}
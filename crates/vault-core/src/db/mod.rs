@@ -0,0 +1,5 @@
+pub mod albums;
+pub mod annotations;
+pub mod database;
+pub mod history;
+pub mod tags;
@@ -0,0 +1,350 @@
+use crate::db::database::refresh_search_text;
+use crate::models::tag::{TagAlias, TagImplication, TagUsage};
+use crate::models::tag_rule::{TagRule, TagRuleConditions, TagRuleInput};
+use rusqlite::{Connection, OptionalExtension, Result, params};
+use std::collections::HashSet;
+
+pub fn resolve_alias(conn: &Connection, tag: &str) -> Result<String> {
+    Ok(conn
+        .query_row(
+            "SELECT tag FROM tag_aliases WHERE alias = ?1",
+            [tag],
+            |row| row.get(0),
+        )
+        .optional()?
+        .unwrap_or_else(|| tag.to_string()))
+}
+
+pub fn expand_tag(conn: &Connection, tag: &str) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare("SELECT implied_tag FROM tag_implications WHERE tag = ?1")?;
+    let mut seen = HashSet::new();
+    let mut tags = Vec::new();
+    let mut pending = vec![resolve_alias(conn, tag)?];
+
+    while let Some(tag) = pending.pop() {
+        if !seen.insert(tag.to_lowercase()) {
+            continue;
+        }
+        for implied in stmt.query_map([&tag], |row| row.get::<_, String>(0))? {
+            pending.push(resolve_alias(conn, &implied?)?);
+        }
+        tags.push(tag);
+    }
+
+    Ok(tags)
+}
+
+pub fn get_tagged_images(conn: &Connection, tag: &str) -> Result<Vec<i64>> {
+    let mut stmt = conn.prepare("SELECT image_id FROM image_tags WHERE tag = ?1")?;
+    stmt.query_map([tag], |row| row.get(0))?.collect()
+}
+
+pub fn get_aliases(conn: &Connection) -> Result<Vec<TagAlias>> {
+    let mut stmt = conn.prepare(
+        "SELECT alias, tag FROM tag_aliases ORDER BY tag COLLATE NOCASE, alias COLLATE NOCASE",
+    )?;
+    stmt.query_map([], |row| {
+        Ok(TagAlias {
+            alias: row.get(0)?,
+            tag: row.get(1)?,
+        })
+    })?
+    .collect()
+}
+
+const TAG_MATCH: &str =
+    "({0} = ?1 OR lower(substr({0}, 1, length(?1) + 1)) IN (lower(?1) || '/', lower(?1) || ':'))";
+const TAG_RENAMED: &str = "?2 || substr({0}, length(?1) + 1)";
+
+fn tag_sql(sql: &str, column: &str) -> String {
+    sql.replace("{match}", &TAG_MATCH.replace("{0}", column))
+        .replace("{renamed}", &TAG_RENAMED.replace("{0}", column))
+}
+
+pub fn get_tag_tree_images(conn: &Connection, tag: &str) -> Result<Vec<i64>> {
+    conn.prepare(&tag_sql(
+        "SELECT DISTINCT image_id FROM image_tags WHERE {match}",
+        "tag",
+    ))?
+    .query_map([tag], |row| row.get(0))?
+    .collect()
+}
+
+pub fn rename_tag(conn: &Connection, from: &str, to: &str) -> Result<()> {
+    let image_ids = get_tag_tree_images(conn, from)?;
+
+    conn.execute(
+        &tag_sql(
+            "DELETE FROM image_tags WHERE {match} AND EXISTS (
+                 SELECT 1 FROM image_tags AS other
+                 WHERE other.rowid <> image_tags.rowid
+                 AND other.image_id = image_tags.image_id
+                 AND other.tag = {renamed})",
+            "image_tags.tag",
+        ),
+        params![from, to],
+    )?;
+    conn.execute(
+        &tag_sql("UPDATE image_tags SET tag = {renamed} WHERE {match}", "tag"),
+        params![from, to],
+    )?;
+
+    conn.execute(
+        &tag_sql(
+            "DELETE FROM tag_implications WHERE {match} AND EXISTS (
+                 SELECT 1 FROM tag_implications AS other
+                 WHERE other.rowid <> tag_implications.rowid
+                 AND other.implied_tag = tag_implications.implied_tag
+                 AND other.tag = {renamed})",
+            "tag_implications.tag",
+        ),
+        params![from, to],
+    )?;
+    conn.execute(
+        &tag_sql(
+            "UPDATE tag_implications SET tag = {renamed} WHERE {match}",
+            "tag",
+        ),
+        params![from, to],
+    )?;
+    conn.execute(
+        &tag_sql(
+            "DELETE FROM tag_implications WHERE {match} AND EXISTS (
+                 SELECT 1 FROM tag_implications AS other
+                 WHERE other.rowid <> tag_implications.rowid
+                 AND other.tag = tag_implications.tag
+                 AND other.implied_tag = {renamed})",
+            "tag_implications.implied_tag",
+        ),
+        params![from, to],
+    )?;
+    conn.execute(
+        &tag_sql(
+            "UPDATE tag_implications SET implied_tag = {renamed} WHERE {match}",
+            "implied_tag",
+        ),
+        params![from, to],
+    )?;
+    conn.execute("DELETE FROM tag_implications WHERE tag = implied_tag", [])?;
+
+    conn.execute(
+        &tag_sql(
+            "UPDATE tag_aliases SET tag = {renamed} WHERE {match}",
+            "tag",
+        ),
+        params![from, to],
+    )?;
+    conn.execute("DELETE FROM tag_aliases WHERE alias = tag", [])?;
+
+    for image_id in image_ids {
+        refresh_search_text(conn, image_id)?;
+    }
+
+    Ok(())
+}
+
+pub fn rename_tags(conn: &Connection, from: &[String], to: &str) -> Result<()> {
+    let to = resolve_alias(conn, to)?;
+    for from in from {
+        rename_tag(conn, from, &to)?;
+    }
+    Ok(())
+}
+
+pub fn insert_alias(conn: &Connection, alias: &str, tag: &str) -> Result<()> {
+    let tag = resolve_alias(conn, tag)?;
+
+    rename_tag(conn, alias, &tag)?;
+    conn.execute(
+        "INSERT OR REPLACE INTO tag_aliases (alias, tag) VALUES (?1, ?2)",
+        params![alias, tag],
+    )?;
+
+    Ok(())
+}
+
+pub fn delete_alias(conn: &Connection, alias: &str) -> Result<usize> {
+    conn.execute("DELETE FROM tag_aliases WHERE alias = ?1", [alias])
+}
+
+pub fn get_implications(conn: &Connection) -> Result<Vec<TagImplication>> {
+    let mut stmt = conn.prepare(
+        "SELECT tag, implied_tag FROM tag_implications
+         ORDER BY tag COLLATE NOCASE, implied_tag COLLATE NOCASE",
+    )?;
+    stmt.query_map([], |row| {
+        Ok(TagImplication {
+            tag: row.get(0)?,
+            implied_tag: row.get(1)?,
+        })
+    })?
+    .collect()
+}
+
+pub fn insert_implication(conn: &Connection, tag: &str, implied_tag: &str) -> Result<usize> {
+    conn.execute(
+        "INSERT OR IGNORE INTO tag_implications (tag, implied_tag) VALUES (?1, ?2)",
+        params![resolve_alias(conn, tag)?, resolve_alias(conn, implied_tag)?],
+    )
+}
+
+pub fn delete_implication(conn: &Connection, tag: &str, implied_tag: &str) -> Result<usize> {
+    conn.execute(
+        "DELETE FROM tag_implications WHERE tag = ?1 AND implied_tag = ?2",
+        params![tag, implied_tag],
+    )
+}
+
+pub fn get_tag_usage(conn: &Connection) -> Result<Vec<TagUsage>> {
+    let mut stmt = conn.prepare(
+        "SELECT tag, COUNT(*), MAX(added_at) FROM image_tags
+         GROUP BY tag
+         ORDER BY COUNT(*) DESC, tag COLLATE NOCASE",
+    )?;
+    stmt.query_map([], |row| {
+        Ok(TagUsage {
+            tag: row.get(0)?,
+            count: row.get::<_, i64>(1)? as usize,
+            last_used: row.get(2)?,
+        })
+    })?
+    .collect()
+}
+
+pub fn get_unused_tags(conn: &Connection) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT name FROM (
+             SELECT tag AS name FROM tag_aliases
+             UNION SELECT tag FROM tag_implications
+             UNION SELECT implied_tag FROM tag_implications
+         )
+         WHERE name NOT IN (SELECT tag FROM image_tags)
+         ORDER BY name COLLATE NOCASE",
+    )?;
+    stmt.query_map([], |row| row.get(0))?.collect()
+}
+
+const RULE_COLUMNS: &str = "id, name, enabled, ocr_pattern, folder, domain, filename_pattern,
+    format, min_width, max_width, min_height, max_height, tags, created_at";
+
+fn rule_from_row(row: &rusqlite::Row) -> Result<TagRule> {
+    Ok(TagRule {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        enabled: row.get(2)?,
+        conditions: TagRuleConditions {
+            ocr_pattern: row.get(3)?,
+            folder: row.get(4)?,
+            domain: row.get(5)?,
+            filename_pattern: row.get(6)?,
+            format: row.get(7)?,
+            min_width: row.get(8)?,
+            max_width: row.get(9)?,
+            min_height: row.get(10)?,
+            max_height: row.get(11)?,
+        },
+        tags: row
+            .get::<_, String>(12)?
+            .split_whitespace()
+            .map(str::to_string)
+            .collect(),
+        created_at: row.get(13)?,
+    })
+}
+
+pub fn get_rules(conn: &Connection) -> Result<Vec<TagRule>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM tag_rules ORDER BY id",
+        RULE_COLUMNS
+    ))?;
+    stmt.query_map([], rule_from_row)?.collect()
+}
+
+pub fn get_rule(conn: &Connection, rule_id: i64) -> Result<TagRule> {
+    conn.query_row(
+        &format!("SELECT {} FROM tag_rules WHERE id = ?1", RULE_COLUMNS),
+        [rule_id],
+        rule_from_row,
+    )
+}
+
+pub fn insert_rule(conn: &Connection, rule: &TagRuleInput) -> Result<i64> {
+    let conditions = &rule.conditions;
+    conn.execute(
+        "INSERT INTO tag_rules (name, enabled, ocr_pattern, folder, domain, filename_pattern,
+             format, min_width, max_width, min_height, max_height, tags)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+        params![
+            rule.name.trim(),
+            rule.enabled,
+            conditions.ocr_pattern,
+            conditions.folder,
+            conditions.domain,
+            conditions.filename_pattern,
+            conditions.format,
+            conditions.min_width,
+            conditions.max_width,
+            conditions.min_height,
+            conditions.max_height,
+            rule.tags.join(" "),
+        ],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+pub fn update_rule(conn: &Connection, rule_id: i64, rule: &TagRuleInput) -> Result<usize> {
+    let conditions = &rule.conditions;
+    conn.execute(
+        "UPDATE tag_rules SET name = ?1, enabled = ?2, ocr_pattern = ?3, folder = ?4, domain = ?5,
+             filename_pattern = ?6, format = ?7, min_width = ?8, max_width = ?9,
+             min_height = ?10, max_height = ?11, tags = ?12
+         WHERE id = ?13",
+        params![
+            rule.name.trim(),
+            rule.enabled,
+            conditions.ocr_pattern,
+            conditions.folder,
+            conditions.domain,
+            conditions.filename_pattern,
+            conditions.format,
+            conditions.min_width,
+            conditions.max_width,
+            conditions.min_height,
+            conditions.max_height,
+            rule.tags.join(" "),
+            rule_id,
+        ],
+    )
+}
+
+pub fn restore_rule(conn: &Connection, rule: &TagRule) -> Result<()> {
+    let conditions = &rule.conditions;
+    conn.execute(
+        &format!(
+            "INSERT OR REPLACE INTO tag_rules ({})
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+            RULE_COLUMNS
+        ),
+        params![
+            rule.id,
+            rule.name,
+            rule.enabled,
+            conditions.ocr_pattern,
+            conditions.folder,
+            conditions.domain,
+            conditions.filename_pattern,
+            conditions.format,
+            conditions.min_width,
+            conditions.max_width,
+            conditions.min_height,
+            conditions.max_height,
+            rule.tags.join(" "),
+            rule.created_at,
+        ],
+    )?;
+    Ok(())
+}
+
+pub fn delete_rule(conn: &Connection, rule_id: i64) -> Result<usize> {
+    conn.execute("DELETE FROM tag_rules WHERE id = ?1", [rule_id])
+}
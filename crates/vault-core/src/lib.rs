@@ -0,0 +1,6 @@
+pub mod db;
+pub mod models;
+pub mod utils;
+pub mod vault;
+
+pub use vault::{Vault, VaultConfig};
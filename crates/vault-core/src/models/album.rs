@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize)]
+pub struct Album {
+    pub id: i64,
+    pub name: String,
+    pub description: String,
+    pub cover_image_id: Option<i64>,
+    pub folder_id: Option<i64>,
+    pub image_count: usize,
+    pub created_at: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct AlbumInput {
+    pub name: String,
+    pub description: String,
+    pub cover_image_id: Option<i64>,
+    pub folder_id: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq)]
+pub struct AlbumFolder {
+    pub id: i64,
+    pub name: String,
+    pub parent_id: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq)]
+pub struct SmartAlbum {
+    pub id: i64,
+    pub name: String,
+    pub query: String,
+    pub pinned: bool,
+    pub image_count: usize,
+    pub created_at: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct SmartAlbumInput {
+    pub name: String,
+    pub query: String,
+    pub pinned: bool,
+}
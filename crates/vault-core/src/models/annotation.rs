@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum AnnotationKind {
+    #[default]
+    Rect,
+    Arrow,
+}
+
+impl AnnotationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnnotationKind::Rect => "rect",
+            AnnotationKind::Arrow => "arrow",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "arrow" => AnnotationKind::Arrow,
+            _ => AnnotationKind::Rect,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct Annotation {
+    pub id: i64,
+    pub image_id: i64,
+    pub kind: AnnotationKind,
+    pub x1: f64,
+    pub y1: f64,
+    pub x2: f64,
+    pub y2: f64,
+    pub color: String,
+    pub text: String,
+    pub created_at: String,
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct AnnotationInput {
+    pub kind: AnnotationKind,
+    pub x1: f64,
+    pub y1: f64,
+    pub x2: f64,
+    pub y2: f64,
+    pub color: String,
+    pub text: String,
+}
+
+impl Default for AnnotationInput {
+    fn default() -> Self {
+        Self {
+            kind: AnnotationKind::Rect,
+            x1: 0.0,
+            y1: 0.0,
+            x2: 0.0,
+            y2: 0.0,
+            color: "#ff3b30".to_string(),
+            text: String::new(),
+        }
+    }
+}
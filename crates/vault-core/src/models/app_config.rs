@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::{network::NetworkConfig, vault::VaultEntry};
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct AppConfig {
+    pub vault_path: Option<String>,
+    pub vaults: Vec<VaultEntry>,
+    pub active_vault: Option<String>,
+    pub api: ApiConfig,
+    pub network: NetworkConfig,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ApiConfig {
+    pub enabled: bool,
+    pub port: u16,
+    pub token: Option<String>,
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        ApiConfig {
+            enabled: false,
+            port: 48620,
+            token: None,
+        }
+    }
+}
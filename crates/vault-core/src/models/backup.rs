@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::image::ImageMarks;
+
+#[derive(Serialize, Deserialize)]
+pub struct BackupFile {
+    pub image_id: i64,
+    pub filename: String,
+    pub hash: String,
+    pub size: u64,
+    pub archive: String,
+    #[serde(flatten)]
+    pub marks: ImageMarks,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub version: u32,
+    pub created_at: String,
+    pub incremental: bool,
+    pub files: Vec<BackupFile>,
+}
+
+#[derive(Serialize)]
+pub struct BackupSummary {
+    pub path: String,
+    pub created_at: String,
+    pub incremental: bool,
+    pub file_count: usize,
+    pub new_files: usize,
+}
+
+#[derive(Serialize)]
+pub struct RestoreSummary {
+    pub images: usize,
+    pub copied_files: usize,
+}
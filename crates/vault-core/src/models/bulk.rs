@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BulkSelection {
+    Ids(Vec<i64>),
+    Query(String),
+}
+
+#[derive(Serialize)]
+pub struct BulkItemResult {
+    pub image_id: i64,
+    pub ok: bool,
+    pub detail: Option<String>,
+}
+
+impl BulkItemResult {
+    pub fn from_result(image_id: i64, result: Result<Option<String>, String>) -> Self {
+        match result {
+            Ok(detail) => BulkItemResult {
+                image_id,
+                ok: true,
+                detail,
+            },
+            Err(e) => BulkItemResult {
+                image_id,
+                ok: false,
+                detail: Some(e),
+            },
+        }
+    }
+}
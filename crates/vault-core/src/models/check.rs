@@ -0,0 +1,11 @@
+use serde::Serialize;
+
+use crate::models::image::Image;
+
+#[derive(Serialize)]
+pub struct CheckReport {
+    pub integrity: String,
+    pub image_count: usize,
+    pub missing_files: Vec<Image>,
+    pub untracked_files: Vec<String>,
+}
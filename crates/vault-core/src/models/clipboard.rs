@@ -0,0 +1,12 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CopyMode {
+    #[default]
+    Pixels,
+    Files,
+    Text,
+    Markdown,
+    Html,
+}
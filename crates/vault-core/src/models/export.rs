@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::image::ImageMarks;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportSelection {
+    Ids(Vec<i64>),
+    Query(String),
+}
+
+#[derive(Deserialize, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportTarget {
+    #[default]
+    Folder,
+    Zip,
+}
+
+#[derive(Deserialize, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ManifestFormat {
+    #[default]
+    Json,
+    Csv,
+    None,
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct ExportOptions {
+    pub target: ExportTarget,
+    pub filename_template: String,
+    pub format: Option<String>,
+    pub max_width: Option<u32>,
+    pub max_height: Option<u32>,
+    pub strip_metadata: bool,
+    pub render_annotations: bool,
+    pub manifest: ManifestFormat,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            target: ExportTarget::Folder,
+            filename_template: "{stem}".to_string(),
+            format: None,
+            max_width: None,
+            max_height: None,
+            strip_metadata: false,
+            render_annotations: false,
+            manifest: ManifestFormat::Json,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ManifestEntry {
+    pub id: i64,
+    pub filename: String,
+    pub exported_as: String,
+    pub added_at: String,
+    pub tags: Vec<String>,
+    pub ocr_text: String,
+    pub notes: String,
+    #[serde(flatten)]
+    pub marks: ImageMarks,
+    pub width: u32,
+    pub height: u32,
+    pub format: String,
+    pub size_bytes: usize,
+}
+
+#[derive(Serialize)]
+pub struct ExportFailure {
+    pub id: i64,
+    pub error: String,
+}
+
+#[derive(Serialize)]
+pub struct ExportSummary {
+    pub destination: String,
+    pub exported: usize,
+    pub failed: Vec<ExportFailure>,
+}
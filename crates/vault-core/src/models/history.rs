@@ -0,0 +1,155 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::{
+    album::{AlbumFolder, SmartAlbum},
+    annotation::Annotation,
+    image::ImageMarks,
+    source::ImageSource,
+    tag::{TagAlias, TagImplication},
+    tag_rule::TagRule,
+};
+
+#[derive(Serialize, Deserialize)]
+pub struct TagChange {
+    pub image_id: i64,
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq)]
+pub struct TagRulesState {
+    pub aliases: Vec<TagAlias>,
+    pub implications: Vec<TagImplication>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MarksChange {
+    pub image_id: i64,
+    pub before: ImageMarks,
+    pub after: ImageMarks,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct NotesChange {
+    pub image_id: i64,
+    pub before: String,
+    pub after: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct OcrChange {
+    pub image_id: i64,
+    pub before: String,
+    pub after: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TagRuleChange {
+    pub rule_id: i64,
+    pub before: Option<TagRule>,
+    pub after: Option<TagRule>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SmartAlbumChange {
+    pub smart_album_id: i64,
+    pub before: Option<SmartAlbum>,
+    pub after: Option<SmartAlbum>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AnnotationChange {
+    pub annotation_id: i64,
+    pub before: Option<Annotation>,
+    pub after: Option<Annotation>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq)]
+pub struct AlbumRecord {
+    pub id: i64,
+    pub name: String,
+    pub description: String,
+    pub cover_image_id: Option<i64>,
+    pub folder_id: Option<i64>,
+    pub created_at: String,
+    pub image_ids: Vec<i64>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AlbumChange {
+    pub album_id: i64,
+    pub before: Option<AlbumRecord>,
+    pub after: Option<AlbumRecord>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AlbumPosition {
+    pub album_id: i64,
+    pub position: i64,
+    pub cover: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DeletedImage {
+    pub id: i64,
+    pub filename: String,
+    pub path: String,
+    pub added_at: String,
+    pub ocr_text: String,
+    pub content_hash: Option<String>,
+    pub trash_file: String,
+    pub tags: Vec<String>,
+    pub source: Option<ImageSource>,
+    pub albums: Vec<AlbumPosition>,
+    #[serde(default)]
+    pub notes: String,
+    #[serde(default)]
+    pub annotations: Vec<Annotation>,
+    #[serde(default)]
+    pub marks: ImageMarks,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Operation {
+    Tags {
+        changes: Vec<TagChange>,
+        rules: Option<(TagRulesState, TagRulesState)>,
+    },
+    DeleteImages {
+        images: Vec<DeletedImage>,
+    },
+    Albums {
+        changes: Vec<AlbumChange>,
+    },
+    Marks {
+        changes: Vec<MarksChange>,
+    },
+    Notes {
+        changes: Vec<NotesChange>,
+    },
+    Annotations {
+        changes: Vec<AnnotationChange>,
+    },
+    Ocr {
+        changes: Vec<OcrChange>,
+    },
+    TagRules {
+        changes: Vec<TagRuleChange>,
+    },
+    SmartAlbums {
+        changes: Vec<SmartAlbumChange>,
+    },
+    AlbumFolders {
+        folders: (Vec<AlbumFolder>, Vec<AlbumFolder>),
+        albums: Vec<AlbumChange>,
+    },
+}
+
+#[derive(Serialize)]
+pub struct HistoryEntry {
+    pub id: i64,
+    pub description: String,
+    pub undone: bool,
+    pub created_at: String,
+}
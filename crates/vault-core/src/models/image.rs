@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::{annotation::Annotation, source::ImageSource};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorLabel {
+    Red,
+    Orange,
+    Yellow,
+    Green,
+    Blue,
+    Purple,
+    Gray,
+}
+
+impl ColorLabel {
+    pub const ALL: [ColorLabel; 7] = [
+        ColorLabel::Red,
+        ColorLabel::Orange,
+        ColorLabel::Yellow,
+        ColorLabel::Green,
+        ColorLabel::Blue,
+        ColorLabel::Purple,
+        ColorLabel::Gray,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ColorLabel::Red => "red",
+            ColorLabel::Orange => "orange",
+            ColorLabel::Yellow => "yellow",
+            ColorLabel::Green => "green",
+            ColorLabel::Blue => "blue",
+            ColorLabel::Purple => "purple",
+            ColorLabel::Gray => "gray",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.to_lowercase();
+        Self::ALL.into_iter().find(|label| label.as_str() == value)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct ImageMarks {
+    pub rating: u8,
+    pub favorite: bool,
+    pub color_label: Option<ColorLabel>,
+}
+
+#[derive(Serialize)]
+pub struct Image {
+    pub id: i64,
+    pub filename: String,
+    pub path: String,
+    pub added_at: String,
+    #[serde(flatten)]
+    pub marks: ImageMarks,
+}
+
+#[derive(Serialize)]
+pub struct ImageDetails {
+    #[serde(flatten)]
+    pub image: Image,
+    pub tags: Vec<String>,
+    pub ocr_text: String,
+    pub source: Option<ImageSource>,
+    pub notes: String,
+    pub annotations: Vec<Annotation>,
+}
@@ -0,0 +1,18 @@
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct MetadataField {
+    pub group: String,
+    pub name: String,
+    pub value: String,
+    pub sensitive: bool,
+}
+
+#[derive(Serialize)]
+pub struct MetadataReport {
+    pub fields: Vec<MetadataField>,
+    pub has_location: bool,
+    pub has_xmp: bool,
+    pub has_icc_profile: bool,
+    pub sensitive_count: usize,
+}
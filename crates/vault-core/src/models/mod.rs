@@ -0,0 +1,21 @@
+pub mod album;
+pub mod annotation;
+pub mod app_config;
+pub mod backup;
+pub mod bulk;
+pub mod check;
+pub mod clipboard;
+pub mod export;
+pub mod history;
+pub mod image;
+pub mod metadata;
+pub mod network;
+pub mod page;
+pub mod search;
+pub mod settings;
+pub mod source;
+pub mod sync_folder;
+pub mod tag;
+pub mod tag_rule;
+pub mod url_import;
+pub mod vault;
@@ -0,0 +1,21 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct DomainRule {
+    pub domain: String,
+    pub headers: BTreeMap<String, String>,
+    pub cookies: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct NetworkConfig {
+    pub proxy: Option<String>,
+    pub no_proxy: Option<String>,
+    pub user_agent: Option<String>,
+    pub ca_certificates: Vec<String>,
+    pub domain_rules: Vec<DomainRule>,
+}
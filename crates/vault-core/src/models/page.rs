@@ -0,0 +1,28 @@
+use serde::Serialize;
+
+#[derive(Serialize, Clone, Copy, PartialEq)]
+pub enum PageImageKind {
+    #[serde(rename = "og:image")]
+    OpenGraph,
+    #[serde(rename = "twitter:image")]
+    Twitter,
+    #[serde(rename = "img")]
+    Img,
+    #[serde(rename = "srcset")]
+    Srcset,
+}
+
+#[derive(Serialize)]
+pub struct PageImage {
+    pub url: String,
+    pub kind: PageImageKind,
+    pub alt: Option<String>,
+    pub width: Option<u32>,
+}
+
+#[derive(Serialize)]
+pub struct PagePreview {
+    pub page_url: String,
+    pub title: Option<String>,
+    pub images: Vec<PageImage>,
+}
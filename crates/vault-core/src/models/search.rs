@@ -0,0 +1,180 @@
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+use crate::models::image::ColorLabel;
+
+pub const MAX_RATING: u8 = 5;
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SortKey {
+    #[default]
+    Added,
+    Filename,
+    Rating,
+    Favorite,
+    Label,
+}
+
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(default)]
+pub struct ImageSort {
+    pub key: SortKey,
+    pub descending: bool,
+}
+
+impl ImageSort {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let (descending, key) = match value.strip_prefix('-') {
+            Some(key) => (true, key),
+            None => (false, value),
+        };
+        let key = match key.to_lowercase().as_str() {
+            "added" | "date" => SortKey::Added,
+            "filename" | "name" => SortKey::Filename,
+            "rating" => SortKey::Rating,
+            "favorite" => SortKey::Favorite,
+            "label" => SortKey::Label,
+            _ => {
+                return Err(format!(
+                    "Invalid sort {}, expected added, filename, rating, favorite or label",
+                    value
+                ));
+            }
+        };
+        Ok(Self { key, descending })
+    }
+
+    pub fn order_by(&self) -> String {
+        let direction = if self.descending { "DESC" } else { "ASC" };
+        let column = match self.key {
+            SortKey::Added => "images.added_at".to_string(),
+            SortKey::Filename => "images.filename COLLATE NOCASE".to_string(),
+            SortKey::Rating => "images.rating".to_string(),
+            SortKey::Favorite => "images.favorite".to_string(),
+            SortKey::Label => {
+                let cases: String = ColorLabel::ALL
+                    .iter()
+                    .enumerate()
+                    .map(|(index, label)| format!(" WHEN '{}' THEN {}", label.as_str(), index))
+                    .collect();
+                format!(
+                    "images.color_label IS NULL, CASE images.color_label{} END",
+                    cases
+                )
+            }
+        };
+        format!("{} {}, images.id {}", column, direction, direction)
+    }
+}
+
+#[derive(Default)]
+pub struct SearchQuery {
+    pub terms: Vec<String>,
+    pub tags: Vec<String>,
+    pub text: Vec<String>,
+    pub sources: Vec<String>,
+    pub formats: Vec<String>,
+    pub after: Option<NaiveDate>,
+    pub before: Option<NaiveDate>,
+    pub favorite: bool,
+    pub min_rating: Option<u8>,
+    pub max_rating: Option<u8>,
+    pub labels: Vec<Option<ColorLabel>>,
+    pub sort: ImageSort,
+}
+
+impl SearchQuery {
+    pub fn parse(query: &str) -> Result<Self, String> {
+        let mut search = SearchQuery::default();
+
+        for token in query.split_whitespace() {
+            let Some((key, value)) = token.split_once(':') else {
+                search.terms.push(token.to_string());
+                continue;
+            };
+            if value.is_empty() {
+                continue;
+            }
+
+            match key.to_lowercase().as_str() {
+                "tag" => search.tags.push(value.to_string()),
+                "text" => search.text.push(value.to_string()),
+                "source" => search.sources.push(value.to_string()),
+                "format" => search
+                    .formats
+                    .push(value.trim_start_matches('.').to_lowercase()),
+                "after" => search.after = Some(parse_date(value)?),
+                "before" => search.before = Some(parse_date(value)?),
+                "is" => match value.to_lowercase().as_str() {
+                    "favorite" | "fav" => search.favorite = true,
+                    "rated" => search.min_rating = Some(1),
+                    "unrated" => search.max_rating = Some(0),
+                    _ => search.terms.push(token.to_string()),
+                },
+                "rating" => search.parse_rating(value)?,
+                "label" if value.eq_ignore_ascii_case("none") => search.labels.push(None),
+                "label" => search.labels.push(Some(
+                    ColorLabel::parse(value)
+                        .ok_or_else(|| format!("Invalid color label {}", value))?,
+                )),
+                "sort" => search.sort = ImageSort::parse(value)?,
+                _ => search.terms.push(token.to_string()),
+            }
+        }
+
+        Ok(search)
+    }
+
+    fn parse_rating(&mut self, value: &str) -> Result<(), String> {
+        let (op, number) = match value.find(|c: char| c.is_ascii_digit()) {
+            Some(index) => value.split_at(index),
+            None => (value, ""),
+        };
+        let rating = number
+            .parse::<u8>()
+            .ok()
+            .filter(|rating| *rating <= MAX_RATING)
+            .ok_or_else(|| format!("Invalid rating {}, expected 0-{}", value, MAX_RATING))?;
+
+        match op {
+            "" | "=" => {
+                self.min_rating = Some(rating);
+                self.max_rating = Some(rating);
+            }
+            ">=" => self.min_rating = Some(rating),
+            ">" => self.min_rating = Some(rating + 1),
+            "<=" => self.max_rating = Some(rating),
+            "<" if rating == 0 => {
+                return Err(format!("Invalid rating {}, no rating is below 0", value));
+            }
+            "<" => self.max_rating = Some(rating - 1),
+            _ => {
+                return Err(format!(
+                    "Invalid rating {}, expected 0-{}",
+                    value, MAX_RATING
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+            && self.tags.is_empty()
+            && self.text.is_empty()
+            && self.sources.is_empty()
+            && self.formats.is_empty()
+            && self.after.is_none()
+            && self.before.is_none()
+            && !self.favorite
+            && self.min_rating.is_none()
+            && self.max_rating.is_none()
+            && self.labels.is_empty()
+    }
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date {}, expected YYYY-MM-DD", value))
+}
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Settings {
+    pub strip_location_on_ingest: bool,
+    pub backup_directory: Option<String>,
+    pub backup_interval_hours: u32,
+    pub backup_incremental: bool,
+}
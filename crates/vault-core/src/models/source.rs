@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceType {
+    #[default]
+    File,
+    Folder,
+    Url,
+    Clipboard,
+    Api,
+}
+
+impl SourceType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SourceType::File => "file",
+            SourceType::Folder => "folder",
+            SourceType::Url => "url",
+            SourceType::Clipboard => "clipboard",
+            SourceType::Api => "api",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "folder" => SourceType::Folder,
+            "url" => SourceType::Url,
+            "clipboard" => SourceType::Clipboard,
+            "api" => SourceType::Api,
+            _ => SourceType::File,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct ImageSource {
+    pub source_type: SourceType,
+    pub origin: Option<String>,
+    pub page_url: Option<String>,
+    pub last_modified: Option<String>,
+    pub etag: Option<String>,
+    pub batch_id: Option<String>,
+    pub imported_at: Option<String>,
+}
+
+impl ImageSource {
+    pub fn new(source_type: SourceType, origin: Option<String>) -> Self {
+        ImageSource {
+            source_type,
+            origin,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_batch(mut self, batch_id: Option<&str>) -> Self {
+        self.batch_id = batch_id.map(str::to_string);
+        self
+    }
+
+    pub fn new_batch_id() -> String {
+        chrono::Utc::now().format("%Y%m%d%H%M%S%3f").to_string()
+    }
+}
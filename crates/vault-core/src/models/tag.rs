@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq)]
+pub struct TagAlias {
+    pub alias: String,
+    pub tag: String,
+}
+
+#[derive(Serialize, Deserialize, PartialEq)]
+pub struct TagImplication {
+    pub tag: String,
+    pub implied_tag: String,
+}
+
+#[derive(Serialize, Clone)]
+pub struct TagUsage {
+    pub tag: String,
+    pub count: usize,
+    pub last_used: Option<String>,
+}
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct TagRuleConditions {
+    pub ocr_pattern: Option<String>,
+    pub folder: Option<String>,
+    pub domain: Option<String>,
+    pub filename_pattern: Option<String>,
+    pub format: Option<String>,
+    pub min_width: Option<u32>,
+    pub max_width: Option<u32>,
+    pub min_height: Option<u32>,
+    pub max_height: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq)]
+pub struct TagRule {
+    pub id: i64,
+    pub name: String,
+    pub enabled: bool,
+    #[serde(flatten)]
+    pub conditions: TagRuleConditions,
+    pub tags: Vec<String>,
+    pub created_at: String,
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct TagRuleInput {
+    pub name: String,
+    pub enabled: bool,
+    #[serde(flatten)]
+    pub conditions: TagRuleConditions,
+    pub tags: Vec<String>,
+}
+
+impl Default for TagRuleInput {
+    fn default() -> Self {
+        TagRuleInput {
+            name: String::new(),
+            enabled: true,
+            conditions: TagRuleConditions::default(),
+            tags: Vec::new(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct TagRuleMatch {
+    pub image_id: i64,
+    pub filename: String,
+    pub tags: Vec<String>,
+}
@@ -0,0 +1,44 @@
+use serde::Serialize;
+
+#[derive(Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum UrlImportStatus {
+    Imported,
+    Duplicate,
+    Failed,
+}
+
+#[derive(Serialize)]
+pub struct UrlImportEntry {
+    pub line: usize,
+    pub url: String,
+    pub status: UrlImportStatus,
+    pub image_id: Option<i64>,
+    pub detail: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct UrlImportReport {
+    pub imported: usize,
+    pub duplicates: usize,
+    pub failed: usize,
+    pub entries: Vec<UrlImportEntry>,
+}
+
+impl UrlImportReport {
+    pub fn new(entries: Vec<UrlImportEntry>) -> Self {
+        let count = |status| {
+            entries
+                .iter()
+                .filter(|entry| entry.status == status)
+                .count()
+        };
+
+        UrlImportReport {
+            imported: count(UrlImportStatus::Imported),
+            duplicates: count(UrlImportStatus::Duplicate),
+            failed: count(UrlImportStatus::Failed),
+            entries,
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct VaultEntry {
+    pub name: String,
+    pub path: String,
+}
+
+#[derive(Serialize)]
+pub struct VaultInfo {
+    pub name: String,
+    pub path: String,
+    pub active: bool,
+    pub available: bool,
+}
+
+#[derive(Serialize)]
+pub struct VaultLocation {
+    pub name: String,
+    pub path: String,
+    pub configured_path: Option<String>,
+    pub available: bool,
+    pub portable: bool,
+}
@@ -0,0 +1,263 @@
+use crate::models::annotation::{Annotation, AnnotationInput, AnnotationKind};
+use image::{Rgba, RgbaImage};
+
+const GLYPH_WIDTH: u32 = 5;
+const GLYPH_HEIGHT: u32 = 8;
+
+const FONT: [[u8; 5]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00],
+    [0x00, 0x00, 0x5F, 0x00, 0x00],
+    [0x00, 0x07, 0x00, 0x07, 0x00],
+    [0x14, 0x7F, 0x14, 0x7F, 0x14],
+    [0x24, 0x2A, 0x7F, 0x2A, 0x12],
+    [0x23, 0x13, 0x08, 0x64, 0x62],
+    [0x36, 0x49, 0x56, 0x20, 0x50],
+    [0x00, 0x08, 0x07, 0x03, 0x00],
+    [0x00, 0x1C, 0x22, 0x41, 0x00],
+    [0x00, 0x41, 0x22, 0x1C, 0x00],
+    [0x2A, 0x1C, 0x7F, 0x1C, 0x2A],
+    [0x08, 0x08, 0x3E, 0x08, 0x08],
+    [0x00, 0x80, 0x70, 0x30, 0x00],
+    [0x08, 0x08, 0x08, 0x08, 0x08],
+    [0x00, 0x00, 0x60, 0x60, 0x00],
+    [0x20, 0x10, 0x08, 0x04, 0x02],
+    [0x3E, 0x51, 0x49, 0x45, 0x3E],
+    [0x00, 0x42, 0x7F, 0x40, 0x00],
+    [0x72, 0x49, 0x49, 0x49, 0x46],
+    [0x21, 0x41, 0x49, 0x4D, 0x33],
+    [0x18, 0x14, 0x12, 0x7F, 0x10],
+    [0x27, 0x45, 0x45, 0x45, 0x39],
+    [0x3C, 0x4A, 0x49, 0x49, 0x31],
+    [0x41, 0x21, 0x11, 0x09, 0x07],
+    [0x36, 0x49, 0x49, 0x49, 0x36],
+    [0x46, 0x49, 0x49, 0x29, 0x1E],
+    [0x00, 0x00, 0x14, 0x00, 0x00],
+    [0x00, 0x40, 0x34, 0x00, 0x00],
+    [0x00, 0x08, 0x14, 0x22, 0x41],
+    [0x14, 0x14, 0x14, 0x14, 0x14],
+    [0x00, 0x41, 0x22, 0x14, 0x08],
+    [0x02, 0x01, 0x59, 0x09, 0x06],
+    [0x3E, 0x41, 0x5D, 0x59, 0x4E],
+    [0x7C, 0x12, 0x11, 0x12, 0x7C],
+    [0x7F, 0x49, 0x49, 0x49, 0x36],
+    [0x3E, 0x41, 0x41, 0x41, 0x22],
+    [0x7F, 0x41, 0x41, 0x41, 0x3E],
+    [0x7F, 0x49, 0x49, 0x49, 0x41],
+    [0x7F, 0x09, 0x09, 0x09, 0x01],
+    [0x3E, 0x41, 0x41, 0x51, 0x73],
+    [0x7F, 0x08, 0x08, 0x08, 0x7F],
+    [0x00, 0x41, 0x7F, 0x41, 0x00],
+    [0x20, 0x40, 0x41, 0x3F, 0x01],
+    [0x7F, 0x08, 0x14, 0x22, 0x41],
+    [0x7F, 0x40, 0x40, 0x40, 0x40],
+    [0x7F, 0x02, 0x1C, 0x02, 0x7F],
+    [0x7F, 0x04, 0x08, 0x10, 0x7F],
+    [0x3E, 0x41, 0x41, 0x41, 0x3E],
+    [0x7F, 0x09, 0x09, 0x09, 0x06],
+    [0x3E, 0x41, 0x51, 0x21, 0x5E],
+    [0x7F, 0x09, 0x19, 0x29, 0x46],
+    [0x26, 0x49, 0x49, 0x49, 0x32],
+    [0x03, 0x01, 0x7F, 0x01, 0x03],
+    [0x3F, 0x40, 0x40, 0x40, 0x3F],
+    [0x1F, 0x20, 0x40, 0x20, 0x1F],
+    [0x3F, 0x40, 0x38, 0x40, 0x3F],
+    [0x63, 0x14, 0x08, 0x14, 0x63],
+    [0x03, 0x04, 0x78, 0x04, 0x03],
+    [0x61, 0x59, 0x49, 0x4D, 0x43],
+    [0x00, 0x7F, 0x41, 0x41, 0x41],
+    [0x02, 0x04, 0x08, 0x10, 0x20],
+    [0x00, 0x41, 0x41, 0x41, 0x7F],
+    [0x04, 0x02, 0x01, 0x02, 0x04],
+    [0x40, 0x40, 0x40, 0x40, 0x40],
+    [0x00, 0x03, 0x07, 0x08, 0x00],
+    [0x20, 0x54, 0x54, 0x78, 0x40],
+    [0x7F, 0x28, 0x44, 0x44, 0x38],
+    [0x38, 0x44, 0x44, 0x44, 0x28],
+    [0x38, 0x44, 0x44, 0x28, 0x7F],
+    [0x38, 0x54, 0x54, 0x54, 0x18],
+    [0x00, 0x08, 0x7E, 0x09, 0x02],
+    [0x18, 0xA4, 0xA4, 0x9C, 0x78],
+    [0x7F, 0x08, 0x04, 0x04, 0x78],
+    [0x00, 0x44, 0x7D, 0x40, 0x00],
+    [0x20, 0x40, 0x40, 0x3D, 0x00],
+    [0x7F, 0x10, 0x28, 0x44, 0x00],
+    [0x00, 0x41, 0x7F, 0x40, 0x00],
+    [0x7C, 0x04, 0x78, 0x04, 0x78],
+    [0x7C, 0x08, 0x04, 0x04, 0x78],
+    [0x38, 0x44, 0x44, 0x44, 0x38],
+    [0xFC, 0x18, 0x24, 0x24, 0x18],
+    [0x18, 0x24, 0x24, 0x18, 0xFC],
+    [0x7C, 0x08, 0x04, 0x04, 0x08],
+    [0x48, 0x54, 0x54, 0x54, 0x24],
+    [0x04, 0x04, 0x3F, 0x44, 0x24],
+    [0x3C, 0x40, 0x40, 0x20, 0x7C],
+    [0x1C, 0x20, 0x40, 0x20, 0x1C],
+    [0x3C, 0x40, 0x30, 0x40, 0x3C],
+    [0x44, 0x28, 0x10, 0x28, 0x44],
+    [0x4C, 0x90, 0x90, 0x90, 0x7C],
+    [0x44, 0x64, 0x54, 0x4C, 0x44],
+    [0x00, 0x08, 0x36, 0x41, 0x00],
+    [0x00, 0x00, 0x77, 0x00, 0x00],
+    [0x00, 0x41, 0x36, 0x08, 0x00],
+    [0x02, 0x01, 0x02, 0x04, 0x02],
+];
+
+pub fn parse_color(color: &str) -> Option<Rgba<u8>> {
+    let hex = color.trim().strip_prefix('#')?;
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some(Rgba([channel(0)?, channel(2)?, channel(4)?, 255]))
+}
+
+pub fn validate_annotation(annotation: &AnnotationInput) -> Result<(), Box<dyn std::error::Error>> {
+    let points = [annotation.x1, annotation.y1, annotation.x2, annotation.y2];
+    if points
+        .iter()
+        .any(|point| !point.is_finite() || *point < 0.0)
+    {
+        return Err("Annotation coordinates must be positive numbers".into());
+    }
+    if parse_color(&annotation.color).is_none() {
+        return Err(format!("Invalid color {}, expected #RRGGBB", annotation.color).into());
+    }
+    Ok(())
+}
+
+fn fill_rect(img: &mut RgbaImage, x: i64, y: i64, width: i64, height: i64, color: Rgba<u8>) {
+    let x_range = x.max(0)..(x + width).min(img.width() as i64);
+    for py in y.max(0)..(y + height).min(img.height() as i64) {
+        for px in x_range.clone() {
+            img.put_pixel(px as u32, py as u32, color);
+        }
+    }
+}
+
+fn draw_line(img: &mut RgbaImage, from: (f64, f64), to: (f64, f64), stroke: u32, color: Rgba<u8>) {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let steps = dx.abs().max(dy.abs()).ceil().max(1.0) as u32;
+    let offset = stroke as f64 / 2.0;
+
+    for step in 0..=steps {
+        let t = step as f64 / steps as f64;
+        let x = (from.0 + dx * t - offset).round() as i64;
+        let y = (from.1 + dy * t - offset).round() as i64;
+        fill_rect(img, x, y, stroke as i64, stroke as i64, color);
+    }
+}
+
+fn draw_label(img: &mut RgbaImage, text: &str, x: f64, y: f64, scale: u32, color: Rgba<u8>) {
+    let text: Vec<char> = text
+        .chars()
+        .map(|c| match c {
+            c if c.is_ascii_graphic() => c,
+            c if c.is_whitespace() => ' ',
+            _ => '?',
+        })
+        .collect();
+    if text.is_empty() {
+        return;
+    }
+
+    let padding = scale as i64 * 2;
+    let advance = (GLYPH_WIDTH + 1) as i64 * scale as i64;
+    let width = advance * text.len() as i64 + padding * 2;
+    let height = GLYPH_HEIGHT as i64 * scale as i64 + padding * 2;
+
+    let x = (x.round() as i64).min(img.width() as i64 - width).max(0);
+    let mut y = y.round() as i64 - height;
+    if y < 0 {
+        y = 0;
+    }
+
+    fill_rect(img, x, y, width, height, color);
+
+    let luminance = 0.299 * color[0] as f64 + 0.587 * color[1] as f64 + 0.114 * color[2] as f64;
+    let ink = if luminance > 150.0 {
+        Rgba([0, 0, 0, 255])
+    } else {
+        Rgba([255, 255, 255, 255])
+    };
+
+    for (index, c) in text.iter().enumerate() {
+        let glyph = FONT[*c as usize - ' ' as usize];
+        let glyph_x = x + padding + advance * index as i64;
+        for (column, bits) in glyph.iter().enumerate() {
+            for row in 0..GLYPH_HEIGHT {
+                if bits & (1 << row) != 0 {
+                    fill_rect(
+                        img,
+                        glyph_x + column as i64 * scale as i64,
+                        y + padding + row as i64 * scale as i64,
+                        scale as i64,
+                        scale as i64,
+                        ink,
+                    );
+                }
+            }
+        }
+    }
+}
+
+pub fn render_annotations(img: &mut RgbaImage, annotations: &[Annotation]) {
+    let stroke = (img.width().min(img.height()) / 200).max(2);
+    let (width, height) = (img.width() as f64, img.height() as f64);
+    let clamp = |value: f64, max: f64| {
+        if value.is_finite() {
+            value.clamp(0.0, max)
+        } else {
+            0.0
+        }
+    };
+
+    for annotation in annotations {
+        let color = parse_color(&annotation.color).unwrap_or(Rgba([255, 59, 48, 255]));
+        let (x1, y1, x2, y2) = (
+            clamp(annotation.x1, width),
+            clamp(annotation.y1, height),
+            clamp(annotation.x2, width),
+            clamp(annotation.y2, height),
+        );
+
+        match annotation.kind {
+            AnnotationKind::Rect => {
+                for (from, to) in [
+                    ((x1, y1), (x2, y1)),
+                    ((x2, y1), (x2, y2)),
+                    ((x2, y2), (x1, y2)),
+                    ((x1, y2), (x1, y1)),
+                ] {
+                    draw_line(img, from, to, stroke, color);
+                }
+            }
+            AnnotationKind::Arrow => {
+                draw_line(img, (x1, y1), (x2, y2), stroke, color);
+
+                let angle = (y2 - y1).atan2(x2 - x1);
+                let head = (stroke * 6) as f64;
+                for side in [-0.5, 0.5] {
+                    let tip = (
+                        x2 - head * (angle + side).cos(),
+                        y2 - head * (angle + side).sin(),
+                    );
+                    draw_line(img, (x2, y2), tip, stroke, color);
+                }
+            }
+        }
+
+        let (label_x, label_y) = match annotation.kind {
+            AnnotationKind::Rect => (x1.min(x2), y1.min(y2)),
+            AnnotationKind::Arrow => (x1, y1),
+        };
+        draw_label(
+            img,
+            &annotation.text,
+            label_x,
+            label_y,
+            stroke / 2 + 1,
+            color,
+        );
+    }
+}
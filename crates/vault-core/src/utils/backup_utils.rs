@@ -0,0 +1,435 @@
+use crate::{
+    db::database,
+    models::{
+        backup::{BackupFile, BackupManifest, BackupSummary, RestoreSummary},
+        image::Image,
+    },
+    utils::{image_utils, path_utils},
+    vault::Vault,
+};
+
+use chrono::Utc;
+use rusqlite::{Connection, MAIN_DB, OpenFlags, backup::Progress, params};
+use std::{
+    collections::{HashMap, HashSet, hash_map::Entry},
+    ffi::OsStr,
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+};
+use zip::{CompressionMethod, ZipArchive, ZipWriter, write::SimpleFileOptions};
+
+pub const BACKUP_PREFIX: &str = "imagevault-backup-";
+const BACKUP_VERSION: u32 = 1;
+const MANIFEST_ENTRY: &str = "backup.json";
+const DATABASE_ENTRY: &str = "imagevault.db";
+
+pub struct PreparedRestore {
+    pub manifest: BackupManifest,
+    pub database: PathBuf,
+    pub staging_dir: PathBuf,
+}
+
+fn file_entry(hash: &str) -> String {
+    format!("files/{}", hash)
+}
+
+pub fn latest_backup(directory: &Path) -> Result<Option<PathBuf>, Box<dyn std::error::Error>> {
+    if !directory.exists() {
+        return Ok(None);
+    }
+
+    let mut archives = Vec::new();
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if name.starts_with(BACKUP_PREFIX) && name.ends_with(".zip") {
+            archives.push(path);
+        }
+    }
+    archives.sort_by(|a, b| b.file_name().cmp(&a.file_name()));
+
+    Ok(archives.into_iter().find(|path| match read_manifest(path) {
+        Ok(_) => true,
+        Err(e) => {
+            eprintln!(
+                "Warning: Skipping unreadable backup {}: {}",
+                path.display(),
+                e
+            );
+            false
+        }
+    }))
+}
+
+pub fn read_manifest(archive_path: &Path) -> Result<BackupManifest, Box<dyn std::error::Error>> {
+    let mut archive = ZipArchive::new(File::open(archive_path)?)?;
+    let manifest = serde_json::from_reader(archive.by_name(MANIFEST_ENTRY)?)?;
+    Ok(manifest)
+}
+
+pub fn backup_vault(
+    vault: &Vault,
+    destination: &Path,
+    incremental: bool,
+) -> Result<BackupSummary, Box<dyn std::error::Error>> {
+    let snapshot = std::env::temp_dir().join(format!(
+        "imagevault-snapshot-{}.db",
+        Utc::now().timestamp_millis()
+    ));
+
+    let images = {
+        let conn = vault.read()?;
+        conn.backup(MAIN_DB, &snapshot, None)?;
+        database::get_images(&conn, vault.root())?
+    };
+
+    let result = create_backup(&snapshot, &images, destination, incremental);
+    let _ = fs::remove_file(&snapshot);
+
+    result
+}
+
+pub fn create_backup(
+    snapshot: &Path,
+    images: &[Image],
+    destination: &Path,
+    incremental: bool,
+) -> Result<BackupSummary, Box<dyn std::error::Error>> {
+    fs::create_dir_all(destination)?;
+
+    let previous: HashMap<String, String> = match latest_backup(destination)? {
+        Some(latest) if incremental => read_manifest(&latest)?
+            .files
+            .into_iter()
+            .map(|file| (file.hash, file.archive))
+            .collect(),
+        _ => HashMap::new(),
+    };
+
+    let created_at = Utc::now();
+    let archive_name = format!(
+        "{}{}.zip",
+        BACKUP_PREFIX,
+        created_at.format("%Y%m%d-%H%M%S%3f")
+    );
+    let archive_path = destination.join(&archive_name);
+    let partial_path = destination.join(format!("{}.partial", archive_name));
+
+    let result = write_backup(
+        &partial_path,
+        &archive_name,
+        &created_at.to_rfc3339(),
+        snapshot,
+        images,
+        &previous,
+    )
+    .and_then(|written| {
+        fs::rename(&partial_path, &archive_path)?;
+        Ok(written)
+    });
+    let (manifest, new_files) = match result {
+        Ok(written) => written,
+        Err(e) => {
+            let _ = fs::remove_file(&partial_path);
+            return Err(e);
+        }
+    };
+
+    Ok(BackupSummary {
+        path: archive_path.to_string_lossy().to_string(),
+        created_at: manifest.created_at,
+        incremental: manifest.incremental,
+        file_count: manifest.files.len(),
+        new_files,
+    })
+}
+
+fn write_backup(
+    path: &Path,
+    archive_name: &str,
+    created_at: &str,
+    snapshot: &Path,
+    images: &[Image],
+    previous: &HashMap<String, String>,
+) -> Result<(BackupManifest, usize), Box<dyn std::error::Error>> {
+    let mut writer = ZipWriter::new(File::create(path)?);
+    let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+
+    writer.start_file(DATABASE_ENTRY, SimpleFileOptions::default())?;
+    io::copy(&mut File::open(snapshot)?, &mut writer)?;
+
+    let mut files = Vec::new();
+    let mut written = HashSet::new();
+
+    for image in images {
+        let path = Path::new(&image.path);
+        let hash = match image_utils::hash_file(path) {
+            Ok(hash) => hash,
+            Err(e) => {
+                eprintln!("Warning: Skipping {} in backup: {}", image.filename, e);
+                continue;
+            }
+        };
+
+        let archive = match previous.get(&hash) {
+            Some(archive) => archive.clone(),
+            None => {
+                if written.insert(hash.clone()) {
+                    writer.start_file(file_entry(&hash), stored)?;
+                    io::copy(&mut File::open(path)?, &mut writer)?;
+                }
+                archive_name.to_string()
+            }
+        };
+
+        files.push(BackupFile {
+            image_id: image.id,
+            filename: image.filename.clone(),
+            size: fs::metadata(path)?.len(),
+            hash,
+            archive,
+            marks: image.marks.clone(),
+        });
+    }
+
+    let manifest = BackupManifest {
+        version: BACKUP_VERSION,
+        created_at: created_at.to_string(),
+        incremental: !previous.is_empty(),
+        files,
+    };
+
+    writer.start_file(MANIFEST_ENTRY, SimpleFileOptions::default())?;
+    serde_json::to_writer_pretty(&mut writer, &manifest)?;
+    writer.finish()?.sync_all()?;
+
+    Ok((manifest, written.len()))
+}
+
+fn validate_database(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+
+    let integrity: String = conn.query_row("PRAGMA integrity_check", [], |row| row.get(0))?;
+    if integrity != "ok" {
+        return Err(format!("Backup database is corrupted: {}", integrity).into());
+    }
+
+    conn.query_row("SELECT COUNT(*) FROM images", [], |row| {
+        row.get::<_, i64>(0)
+    })?;
+
+    Ok(())
+}
+
+pub fn prepare_restore(
+    archive_path: &Path,
+    staging_dir: &Path,
+) -> Result<PreparedRestore, Box<dyn std::error::Error>> {
+    let manifest = read_manifest(archive_path)?;
+    if manifest.version > BACKUP_VERSION {
+        return Err("Backup was created by a newer version of ImageVault".into());
+    }
+
+    let backup_dir = archive_path.parent().ok_or("Invalid backup path")?;
+
+    if staging_dir.exists() {
+        fs::remove_dir_all(staging_dir)?;
+    }
+    fs::create_dir_all(staging_dir)?;
+
+    let database = staging_dir.join(DATABASE_ENTRY);
+    let mut archive = ZipArchive::new(File::open(archive_path)?)?;
+    io::copy(
+        &mut archive.by_name(DATABASE_ENTRY)?,
+        &mut File::create(&database)?,
+    )?;
+    validate_database(&database)?;
+
+    let mut archives: HashMap<String, ZipArchive<File>> = HashMap::new();
+
+    for file in &manifest.files {
+        let target = staging_dir.join(&file.hash);
+        if target.exists() {
+            continue;
+        }
+
+        let archive = match archives.entry(file.archive.clone()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let source = File::open(backup_dir.join(&file.archive))
+                    .map_err(|e| format!("Missing backup archive {}: {}", file.archive, e))?;
+                entry.insert(ZipArchive::new(source)?)
+            }
+        };
+
+        io::copy(
+            &mut archive.by_name(&file_entry(&file.hash))?,
+            &mut File::create(&target)?,
+        )?;
+
+        if image_utils::hash_file(&target)? != file.hash {
+            return Err(format!("Checksum mismatch for {}", file.filename).into());
+        }
+    }
+
+    Ok(PreparedRestore {
+        manifest,
+        database,
+        staging_dir: staging_dir.to_path_buf(),
+    })
+}
+
+fn link_or_copy(from: &Path, to: &Path) -> io::Result<()> {
+    fs::hard_link(from, to).or_else(|_| fs::copy(from, to).map(|_| ()))
+}
+
+struct StagedFile {
+    image_id: i64,
+    path: PathBuf,
+    copied: bool,
+}
+
+fn stage_images(
+    image_dir: &Path,
+    staged_images: &Path,
+    prepared: &PreparedRestore,
+) -> Result<Vec<StagedFile>, Box<dyn std::error::Error>> {
+    if staged_images.exists() {
+        fs::remove_dir_all(staged_images)?;
+    }
+    fs::create_dir_all(staged_images)?;
+
+    let mut staged = Vec::new();
+
+    for file in &prepared.manifest.files {
+        let path = image_utils::unique_path(staged_images, OsStr::new(&file.filename));
+        let existing = image_dir.join(&file.filename);
+        let copied = if existing.is_file() && image_utils::hash_file(&existing)? == file.hash {
+            link_or_copy(&existing, &path)?;
+            false
+        } else {
+            fs::copy(prepared.staging_dir.join(&file.hash), &path)?;
+            true
+        };
+        staged.push(StagedFile {
+            image_id: file.image_id,
+            path,
+            copied,
+        });
+    }
+
+    Ok(staged)
+}
+
+fn rewrite_paths(
+    database: &Path,
+    root: &Path,
+    image_dir: &Path,
+    staged: &[StagedFile],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut conn = Connection::open(database)?;
+    database::run_migrations(&conn)?;
+
+    let tx = conn.transaction()?;
+    for file in staged {
+        let filename = file
+            .path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or("Invalid filename")?;
+        tx.execute(
+            "UPDATE images SET path = ?1, filename = ?2 WHERE id = ?3",
+            params![
+                path_utils::to_stored_path(root, &image_dir.join(filename)),
+                filename,
+                file.image_id
+            ],
+        )?;
+    }
+    tx.commit()?;
+
+    Ok(())
+}
+
+fn swap_images(image_dir: &Path, staged_images: &Path) -> io::Result<PathBuf> {
+    let previous = image_dir.with_file_name(".restore-previous");
+    if previous.exists() {
+        fs::remove_dir_all(&previous)?;
+    }
+    if image_dir.exists() {
+        fs::rename(image_dir, &previous)?;
+    }
+    if let Err(e) = fs::rename(staged_images, image_dir) {
+        let _ = fs::rename(&previous, image_dir);
+        return Err(e);
+    }
+    Ok(previous)
+}
+
+fn unswap_images(image_dir: &Path, previous: &Path) -> io::Result<()> {
+    fs::remove_dir_all(image_dir)?;
+    if previous.exists() {
+        fs::rename(previous, image_dir)?;
+    }
+    Ok(())
+}
+
+pub fn apply_restore(
+    conn: &mut Connection,
+    root: &Path,
+    prepared: &PreparedRestore,
+) -> Result<RestoreSummary, Box<dyn std::error::Error>> {
+    let image_dir = root.join(path_utils::IMAGES_DIR);
+    let staged_images = root.join(".restore-images");
+
+    let result = stage_images(&image_dir, &staged_images, prepared).and_then(|staged| {
+        rewrite_paths(&prepared.database, root, &image_dir, &staged)?;
+        let previous = swap_images(&image_dir, &staged_images)?;
+
+        if let Err(e) = conn.restore(MAIN_DB, &prepared.database, None::<fn(Progress)>) {
+            return Err(match unswap_images(&image_dir, &previous) {
+                Ok(()) => e.into(),
+                Err(unswap) => format!(
+                    "Restore failed and left the Images folder half restored ({}); the previous images are in {}: {}",
+                    unswap,
+                    previous.display(),
+                    e
+                )
+                .into(),
+            });
+        }
+
+        if let Err(e) = fs::remove_dir_all(&previous)
+            && previous.exists()
+        {
+            eprintln!("Warning: Could not remove {}: {}", previous.display(), e);
+        }
+        Ok(staged.iter().filter(|file| file.copied).count())
+    });
+
+    if staged_images.exists() {
+        let _ = fs::remove_dir_all(&staged_images);
+    }
+
+    Ok(RestoreSummary {
+        images: prepared.manifest.files.len(),
+        copied_files: result?,
+    })
+}
+
+pub fn restore_vault(
+    vault: &Vault,
+    archive_path: &Path,
+) -> Result<RestoreSummary, Box<dyn std::error::Error>> {
+    let staging_dir = vault.root().join(".restore");
+
+    let result = prepare_restore(archive_path, &staging_dir).and_then(|prepared| {
+        let mut conn = vault.write()?;
+        apply_restore(&mut conn, vault.root(), &prepared)
+    });
+
+    let _ = fs::remove_dir_all(&staging_dir);
+
+    result
+}
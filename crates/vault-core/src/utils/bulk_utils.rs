@@ -0,0 +1,215 @@
+use crate::{
+    db::{albums, database, history},
+    models::{
+        bulk::{BulkItemResult, BulkSelection},
+        history::Operation,
+        image::Image,
+    },
+    utils::history_utils,
+    vault::Vault,
+};
+use rusqlite::{Connection, Transaction};
+use std::collections::{HashMap, HashSet};
+
+type ItemResult = Result<Option<String>, Box<dyn std::error::Error>>;
+
+fn selected_ids(
+    vault: &Vault,
+    selection: BulkSelection,
+) -> Result<Vec<i64>, Box<dyn std::error::Error>> {
+    let mut ids = match selection {
+        BulkSelection::Ids(ids) => ids,
+        BulkSelection::Query(query) if query.trim().is_empty() => {
+            vault.images()?.iter().map(|image| image.id).collect()
+        }
+        BulkSelection::Query(query) => vault.search(&query)?.iter().map(|image| image.id).collect(),
+    };
+
+    let mut seen = HashSet::new();
+    ids.retain(|id| seen.insert(*id));
+    Ok(ids)
+}
+
+fn run_bulk(
+    vault: &Vault,
+    tx: &mut Transaction,
+    image_ids: &[i64],
+    mut f: impl FnMut(&Connection, &Image) -> ItemResult,
+) -> Result<Vec<BulkItemResult>, Box<dyn std::error::Error>> {
+    let mut results = Vec::with_capacity(image_ids.len());
+
+    for &image_id in image_ids {
+        let savepoint = tx.savepoint()?;
+        let result = database::get_image(&savepoint, vault.root(), image_id)
+            .map_err(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => "Image not found".into(),
+                e => e.into(),
+            })
+            .and_then(|image| f(&savepoint, &image));
+
+        if result.is_ok() {
+            savepoint.commit()?;
+        }
+        results.push(BulkItemResult::from_result(
+            image_id,
+            result.map_err(|e| e.to_string()),
+        ));
+    }
+
+    Ok(results)
+}
+
+pub fn add_tags(
+    vault: &Vault,
+    selection: BulkSelection,
+    tags: &[String],
+) -> Result<Vec<BulkItemResult>, Box<dyn std::error::Error>> {
+    let image_ids = selected_ids(vault, selection)?;
+
+    let mut conn = vault.write()?;
+    let mut tx = conn.transaction()?;
+    let before = history::tag_states(&tx, &image_ids)?;
+    let results = run_bulk(vault, &mut tx, &image_ids, |conn, image| {
+        for tag in tags {
+            database::insert_tag(conn, image.id, tag)?;
+        }
+        Ok(None)
+    })?;
+    history::record_tags(
+        &tx,
+        &format!("Add tags {} to {} images", tags.join(", "), image_ids.len()),
+        before,
+        None,
+    )?;
+    tx.commit()?;
+
+    Ok(results)
+}
+
+pub fn remove_tags(
+    vault: &Vault,
+    selection: BulkSelection,
+    tags: &[String],
+) -> Result<Vec<BulkItemResult>, Box<dyn std::error::Error>> {
+    let image_ids = selected_ids(vault, selection)?;
+
+    let mut conn = vault.write()?;
+    let mut tx = conn.transaction()?;
+    let before = history::tag_states(&tx, &image_ids)?;
+    let results = run_bulk(vault, &mut tx, &image_ids, |conn, image| {
+        for tag in tags {
+            database::remove_tag(conn, image.id, tag)?;
+        }
+        Ok(None)
+    })?;
+    history::record_tags(
+        &tx,
+        &format!(
+            "Remove tags {} from {} images",
+            tags.join(", "),
+            image_ids.len()
+        ),
+        before,
+        None,
+    )?;
+    tx.commit()?;
+
+    Ok(results)
+}
+
+pub fn delete_images(
+    vault: &Vault,
+    selection: BulkSelection,
+) -> Result<Vec<BulkItemResult>, Box<dyn std::error::Error>> {
+    let image_ids = selected_ids(vault, selection)?;
+
+    let mut conn = vault.write()?;
+    let mut tx = conn.transaction()?;
+    let mut images = Vec::new();
+    let results = run_bulk(vault, &mut tx, &image_ids, |conn, image| {
+        images.push(history_utils::trash_record(conn, vault.root(), image)?);
+        Ok(None)
+    })?;
+
+    if images.is_empty() {
+        tx.commit()?;
+        return Ok(results);
+    }
+
+    let description = format!("Delete {} images", images.len());
+    let operation = Operation::DeleteImages { images };
+    history::record(&tx, &description, &operation)?;
+    tx.commit()?;
+
+    history_utils::move_to_trash(vault.root(), &operation);
+    Ok(results)
+}
+
+pub fn ocr_retry(
+    vault: &Vault,
+    selection: BulkSelection,
+) -> Result<Vec<BulkItemResult>, Box<dyn std::error::Error>> {
+    let image_ids = selected_ids(vault, selection)?;
+    let mut texts: HashMap<i64, Result<String, String>> = image_ids
+        .iter()
+        .map(|&image_id| {
+            let text = vault
+                .image(image_id)
+                .and_then(|image| vault.extract_text(&image.path))
+                .map_err(|e| e.to_string());
+            (image_id, text)
+        })
+        .collect();
+
+    let mut conn = vault.write()?;
+    let mut tx = conn.transaction()?;
+    let before = history::ocr_states(&tx, &image_ids)?;
+    let results = run_bulk(vault, &mut tx, &image_ids, |conn, image| {
+        let text = texts.remove(&image.id).ok_or("Missing OCR result")??;
+        database::set_ocr_text(conn, image.id, &text)?;
+        Ok(Some(text))
+    })?;
+    history::record_ocr(
+        &tx,
+        &format!("Re-run OCR on {} images", image_ids.len()),
+        before,
+    )?;
+    tx.commit()?;
+
+    Ok(results)
+}
+
+pub fn move_to_album(
+    vault: &Vault,
+    selection: BulkSelection,
+    album_id: i64,
+    from_album_id: Option<i64>,
+) -> Result<Vec<BulkItemResult>, Box<dyn std::error::Error>> {
+    vault.album(album_id)?;
+    if let Some(from_album_id) = from_album_id {
+        vault.album(from_album_id)?;
+    }
+
+    let image_ids = selected_ids(vault, selection)?;
+    let from_album_id = from_album_id.filter(|id| *id != album_id);
+    let album_ids: Vec<i64> = std::iter::once(album_id).chain(from_album_id).collect();
+
+    let mut conn = vault.write()?;
+    let mut tx = conn.transaction()?;
+    let before = history::album_states(&tx, &album_ids)?;
+    let results = run_bulk(vault, &mut tx, &image_ids, |conn, image| {
+        albums::insert_album_images(conn, album_id, &[image.id])?;
+        if let Some(from_album_id) = from_album_id {
+            albums::delete_album_images(conn, from_album_id, &[image.id])?;
+        }
+        Ok(None)
+    })?;
+    history::record_albums(
+        &tx,
+        &format!("Move {} images to album", image_ids.len()),
+        before,
+    )?;
+    tx.commit()?;
+
+    Ok(results)
+}
@@ -0,0 +1,255 @@
+use arboard::Clipboard;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::{
+    Vault,
+    models::{
+        clipboard::CopyMode,
+        image::Image,
+        source::{ImageSource, SourceType},
+    },
+    utils::image_utils,
+};
+
+pub enum ClipboardContent {
+    Files(Vec<PathBuf>),
+    Image(Vec<u8>),
+    DataUrl(String),
+    Urls(Vec<String>),
+}
+
+pub fn copy_image(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let mut clipboard = Clipboard::new()?;
+
+    let bytes = fs::read(path)?;
+
+    let img = image::load_from_memory(&bytes)?;
+    let rgba = img.to_rgba8();
+
+    let (width, height) = rgba.dimensions();
+
+    clipboard.set_image(arboard::ImageData {
+        width: width as usize,
+        height: height as usize,
+        bytes: std::borrow::Cow::Owned(rgba.into_raw()),
+    })?;
+
+    Ok(())
+}
+
+fn file_uri(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let encoded: String = path
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect();
+
+    if encoded.starts_with('/') {
+        format!("file://{}", encoded)
+    } else {
+        format!("file:///{}", encoded)
+    }
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub fn copy_images(
+    vault: &Vault,
+    image_ids: &[i64],
+    mode: CopyMode,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let images = image_ids
+        .iter()
+        .map(|id| vault.image(*id))
+        .collect::<Result<Vec<_>, _>>()?;
+    if images.is_empty() {
+        return Err("No images were selected".into());
+    }
+
+    match mode {
+        CopyMode::Pixels => match images.as_slice() {
+            [image] => copy_image(Path::new(&image.path)),
+            _ => Err("Only one image can be copied as pixels at a time".into()),
+        },
+        CopyMode::Files => {
+            let paths: Vec<&str> = images.iter().map(|image| image.path.as_str()).collect();
+            Ok(Clipboard::new()?.set().file_list(&paths)?)
+        }
+        CopyMode::Text => {
+            let text = images
+                .iter()
+                .map(|image| vault.ocr_text(image.id))
+                .collect::<Result<Vec<_>, _>>()?
+                .iter()
+                .map(|text| text.trim())
+                .filter(|text| !text.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            if text.is_empty() {
+                return Err("The selected images have no recognized text".into());
+            }
+            Ok(Clipboard::new()?.set_text(text)?)
+        }
+        CopyMode::Markdown => {
+            let markdown = images
+                .iter()
+                .map(|image| format!("![{}]({})", image.filename, file_uri(&image.path)))
+                .collect::<Vec<_>>()
+                .join("\n");
+            Ok(Clipboard::new()?.set_text(markdown)?)
+        }
+        CopyMode::Html => {
+            let html = images
+                .iter()
+                .map(|image| {
+                    format!(
+                        "<img src=\"{}\" alt=\"{}\">",
+                        escape_html(&file_uri(&image.path)),
+                        escape_html(&image.filename)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            let alt_text = images
+                .iter()
+                .map(|image| image.path.clone())
+                .collect::<Vec<_>>()
+                .join("\n");
+            Ok(Clipboard::new()?.set_html(html, Some(alt_text))?)
+        }
+    }
+}
+
+fn encode_png(data: arboard::ImageData) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let rgba = image::RgbaImage::from_raw(
+        data.width as u32,
+        data.height as u32,
+        data.bytes.into_owned(),
+    )
+    .ok_or("The clipboard image is malformed")?;
+
+    let mut png = Vec::new();
+    image::DynamicImage::ImageRgba8(rgba)
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)?;
+    Ok(png)
+}
+
+pub fn read_clipboard() -> Result<ClipboardContent, Box<dyn std::error::Error>> {
+    let mut clipboard = Clipboard::new()?;
+
+    if let Ok(files) = clipboard.get().file_list()
+        && !files.is_empty()
+    {
+        return Ok(ClipboardContent::Files(files));
+    }
+
+    if let Ok(data) = clipboard.get_image() {
+        return Ok(ClipboardContent::Image(encode_png(data)?));
+    }
+
+    let text = clipboard
+        .get_text()
+        .map_err(|_| "The clipboard does not contain an image")?;
+    let text = text.trim();
+
+    if text.starts_with("data:") {
+        return Ok(ClipboardContent::DataUrl(text.to_string()));
+    }
+
+    let lines: Vec<&str> = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    if !lines.is_empty()
+        && lines
+            .iter()
+            .all(|line| line.starts_with("http://") || line.starts_with("https://"))
+    {
+        return Ok(ClipboardContent::Urls(
+            lines.into_iter().map(str::to_string).collect(),
+        ));
+    }
+
+    let files: Vec<PathBuf> = lines
+        .iter()
+        .map(|line| PathBuf::from(line.strip_prefix("file://").unwrap_or(line)))
+        .collect();
+    if !files.is_empty() && files.iter().all(|file| file.is_file()) {
+        return Ok(ClipboardContent::Files(files));
+    }
+
+    Err("The clipboard does not contain an image, image file or image URL".into())
+}
+
+fn import_clipboard_file(vault: &Vault, path: &Path) -> Result<Image, Box<dyn std::error::Error>> {
+    let format = image::ImageReader::open(path)?
+        .with_guessed_format()?
+        .format();
+    if format.is_none() {
+        return Err(format!("{} is not an image", path.display()).into());
+    }
+
+    vault.import_local(path, false, SourceType::Clipboard, None)
+}
+
+fn import_content(
+    vault: &Vault,
+    content: ClipboardContent,
+) -> Result<Vec<Image>, Box<dyn std::error::Error>> {
+    let source = ImageSource::new(SourceType::Clipboard, None);
+
+    match content {
+        ClipboardContent::Files(files) => files
+            .iter()
+            .map(|path| import_clipboard_file(vault, path))
+            .collect(),
+        ClipboardContent::Image(png) => {
+            let filename = image_utils::pasted_filename("png");
+            Ok(vec![vault.import_bytes(&filename, &png, &source)?])
+        }
+        ClipboardContent::DataUrl(data_url) => {
+            let (bytes, extension) = image_utils::decode_data_url(&data_url)?;
+            let filename = image_utils::pasted_filename(extension);
+            Ok(vec![vault.import_bytes(&filename, &bytes, &source)?])
+        }
+        ClipboardContent::Urls(_) => Err("Importing from URLs is not enabled".into()),
+    }
+}
+
+pub async fn paste(vault: Arc<Vault>) -> Result<Vec<Image>, Box<dyn std::error::Error>> {
+    let content =
+        tokio::task::spawn_blocking(|| read_clipboard().map_err(|e| e.to_string())).await??;
+
+    match content {
+        #[cfg(feature = "network")]
+        ClipboardContent::Urls(urls) => {
+            let mut images = Vec::new();
+            for url in urls {
+                images.push(vault.fetch_url(&url).await?);
+            }
+            Ok(images)
+        }
+        content => tokio::task::spawn_blocking(move || {
+            import_content(&vault, content).map_err(|e| e.to_string())
+        })
+        .await?
+        .map_err(Into::into),
+    }
+}
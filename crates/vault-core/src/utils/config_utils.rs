@@ -0,0 +1,87 @@
+use crate::models::app_config::AppConfig;
+
+use dirs_next::data_dir;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_VAULT: &str = "Default";
+const CONFIG_FILE: &str = "config.json";
+const PORTABLE_DIR: &str = "ImageVaultData";
+
+pub fn get_portable_dir() -> Option<PathBuf> {
+    let dir = std::env::current_exe().ok()?.parent()?.join(PORTABLE_DIR);
+    dir.is_dir().then_some(dir)
+}
+
+pub fn get_config_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    if let Some(dir) = get_portable_dir() {
+        return Ok(dir);
+    }
+
+    let mut path = data_dir().ok_or("No data dir found")?;
+    path.push("ImageVault");
+    fs::create_dir_all(&path)?;
+    Ok(path)
+}
+
+pub fn load_config() -> Result<AppConfig, Box<dyn std::error::Error>> {
+    let path = get_config_dir()?.join(CONFIG_FILE);
+    if !path.exists() {
+        return Ok(AppConfig::default());
+    }
+
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+pub fn save_config(config: &AppConfig) -> Result<(), Box<dyn std::error::Error>> {
+    let path = get_config_dir()?.join(CONFIG_FILE);
+    fs::write(path, serde_json::to_string_pretty(config)?)?;
+    Ok(())
+}
+
+pub fn active_vault_name(config: &AppConfig) -> String {
+    config
+        .active_vault
+        .clone()
+        .unwrap_or_else(|| DEFAULT_VAULT.to_string())
+}
+
+pub fn get_vault_path(
+    config: &AppConfig,
+    name: &str,
+) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    if name == DEFAULT_VAULT {
+        return match &config.vault_path {
+            Some(path) => Ok(PathBuf::from(path)),
+            None => get_config_dir(),
+        };
+    }
+
+    config
+        .vaults
+        .iter()
+        .find(|vault| vault.name == name)
+        .map(|vault| PathBuf::from(&vault.path))
+        .ok_or_else(|| format!("Unknown vault {}", name).into())
+}
+
+pub fn set_vault_path(config: &mut AppConfig, name: &str, path: &Path) {
+    let path = path.to_string_lossy().to_string();
+
+    if name == DEFAULT_VAULT {
+        config.vault_path = Some(path);
+    } else if let Some(vault) = config.vaults.iter_mut().find(|vault| vault.name == name) {
+        vault.path = path;
+    }
+}
+
+pub fn resolve_vault_root() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let config = load_config()?;
+    let path = get_vault_path(&config, &active_vault_name(&config))?;
+
+    if !path.is_dir() {
+        return Err(format!("Vault location {} is unavailable", path.display()).into());
+    }
+
+    Ok(path)
+}
@@ -0,0 +1,419 @@
+use crate::{
+    db::{annotations, database},
+    models::{
+        annotation::Annotation,
+        export::{
+            ExportFailure, ExportOptions, ExportSelection, ExportSummary, ExportTarget,
+            ManifestEntry, ManifestFormat,
+        },
+        image::Image,
+        search::SearchQuery,
+    },
+    utils::{annotation_utils, image_utils, metadata_utils},
+    vault::Vault,
+};
+
+use image::{DynamicImage, ImageFormat, ImageReader, imageops::FilterType};
+use std::{
+    collections::HashSet,
+    ffi::OsStr,
+    fs::{self, File},
+    io::{Cursor, Write},
+    path::{Path, PathBuf},
+};
+use zip::{ZipWriter, write::SimpleFileOptions};
+
+pub struct PreparedImage {
+    pub bytes: Vec<u8>,
+    pub extension: String,
+    pub format: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+type ManifestFile = (&'static str, Vec<u8>);
+type ExportDetails = (Image, Vec<String>, String, String, Vec<Annotation>);
+
+pub enum ExportSink {
+    Folder(PathBuf),
+    Zip {
+        writer: Box<ZipWriter<File>>,
+        names: HashSet<String>,
+        partial: PathBuf,
+        destination: PathBuf,
+    },
+}
+
+impl ExportSink {
+    pub fn create(
+        destination: &Path,
+        target: &ExportTarget,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        match target {
+            ExportTarget::Folder => {
+                fs::create_dir_all(destination)?;
+                Ok(Self::Folder(destination.to_path_buf()))
+            }
+            ExportTarget::Zip => {
+                if let Some(parent) = destination.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                let name = destination
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .ok_or("Invalid export destination")?;
+                let partial = destination.with_file_name(format!("{}.partial", name));
+                Ok(Self::Zip {
+                    writer: Box::new(ZipWriter::new(File::create(&partial)?)),
+                    names: HashSet::new(),
+                    partial,
+                    destination: destination.to_path_buf(),
+                })
+            }
+        }
+    }
+
+    pub fn add_file(
+        &mut self,
+        name: &str,
+        bytes: &[u8],
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        match self {
+            Self::Folder(dir) => {
+                let path = image_utils::unique_path(dir, OsStr::new(name));
+                fs::write(&path, bytes)?;
+                Ok(path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or(name)
+                    .to_string())
+            }
+            Self::Zip { writer, names, .. } => {
+                let name = image_utils::unique_file_name(name, |n| names.contains(n));
+                writer.start_file(name.as_str(), SimpleFileOptions::default())?;
+                writer.write_all(bytes)?;
+                names.insert(name.clone());
+                Ok(name)
+            }
+        }
+    }
+
+    pub fn finish(self) -> Result<(), Box<dyn std::error::Error>> {
+        if let Self::Zip {
+            writer,
+            partial,
+            destination,
+            ..
+        } = self
+        {
+            let result = writer
+                .finish()
+                .map_err(Into::into)
+                .and_then(|file| file.sync_all().map_err(Into::into))
+                .and_then(|_| fs::rename(&partial, &destination).map_err(Into::into));
+            if result.is_err() {
+                let _ = fs::remove_file(&partial);
+            }
+            return result;
+        }
+        Ok(())
+    }
+
+    pub fn abort(self) {
+        if let Self::Zip {
+            writer, partial, ..
+        } = self
+        {
+            drop(writer);
+            let _ = fs::remove_file(&partial);
+        }
+    }
+}
+
+fn format_name(format: ImageFormat) -> String {
+    format!("{:?}", format).to_lowercase()
+}
+
+fn format_extension(format: ImageFormat) -> String {
+    format
+        .extensions_str()
+        .first()
+        .copied()
+        .unwrap_or("img")
+        .to_string()
+}
+
+pub fn prepare_image(
+    path: &str,
+    options: &ExportOptions,
+    annotations: &[Annotation],
+) -> Result<PreparedImage, Box<dyn std::error::Error>> {
+    let bytes = fs::read(path)?;
+    let source_format = image::guess_format(&bytes)?;
+    let target_format = match &options.format {
+        Some(ext) => ImageFormat::from_extension(ext)
+            .ok_or_else(|| format!("Unsupported export format: {}", ext))?,
+        None => source_format,
+    };
+    let needs_resize = options.max_width.is_some() || options.max_height.is_some();
+    let needs_render = options.render_annotations && !annotations.is_empty();
+
+    if target_format == source_format && !needs_resize && !needs_render {
+        let (width, height) =
+            ImageReader::with_format(Cursor::new(&bytes), source_format).into_dimensions()?;
+        let bytes = if options.strip_metadata {
+            metadata_utils::strip_metadata(&bytes)?
+        } else {
+            bytes
+        };
+
+        return Ok(PreparedImage {
+            bytes,
+            extension: format_extension(source_format),
+            format: format_name(source_format),
+            width,
+            height,
+        });
+    }
+
+    let mut img = image::load_from_memory_with_format(&bytes, source_format)?;
+
+    if needs_render {
+        let mut rgba = img.to_rgba8();
+        annotation_utils::render_annotations(&mut rgba, annotations);
+        img = DynamicImage::ImageRgba8(rgba);
+    }
+
+    let max_width = options.max_width.unwrap_or(u32::MAX);
+    let max_height = options.max_height.unwrap_or(u32::MAX);
+    if img.width() > max_width || img.height() > max_height {
+        img = img.resize(
+            max_width.min(img.width()),
+            max_height.min(img.height()),
+            FilterType::Lanczos3,
+        );
+    }
+
+    if target_format == ImageFormat::Jpeg {
+        img = DynamicImage::ImageRgb8(img.to_rgb8());
+    }
+
+    let mut out = Cursor::new(Vec::new());
+    img.write_to(&mut out, target_format)?;
+
+    Ok(PreparedImage {
+        bytes: out.into_inner(),
+        extension: format_extension(target_format),
+        format: format_name(target_format),
+        width: img.width(),
+        height: img.height(),
+    })
+}
+
+pub fn render_filename(template: &str, image: &Image, index: usize, extension: &str) -> String {
+    let stem = Path::new(&image.filename)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("image");
+    let date = image.added_at.split(' ').next().unwrap_or("");
+
+    let name = template
+        .replace("{id}", &image.id.to_string())
+        .replace("{index}", &index.to_string())
+        .replace("{stem}", stem)
+        .replace("{date}", date);
+    let name = image_utils::sanitize_filename(name.trim());
+
+    if name.is_empty() {
+        format!("image_{}.{}", image.id, extension)
+    } else {
+        format!("{}.{}", name, extension)
+    }
+}
+
+pub fn render_manifest(
+    entries: &[ManifestEntry],
+    format: &ManifestFormat,
+) -> Result<Option<ManifestFile>, Box<dyn std::error::Error>> {
+    match format {
+        ManifestFormat::Json => Ok(Some(("manifest.json", serde_json::to_vec_pretty(entries)?))),
+        ManifestFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            writer.write_record([
+                "id",
+                "filename",
+                "exported_as",
+                "added_at",
+                "tags",
+                "ocr_text",
+                "notes",
+                "rating",
+                "favorite",
+                "color_label",
+                "width",
+                "height",
+                "format",
+                "size_bytes",
+            ])?;
+
+            for entry in entries {
+                writer.write_record([
+                    entry.id.to_string(),
+                    entry.filename.clone(),
+                    entry.exported_as.clone(),
+                    entry.added_at.clone(),
+                    entry.tags.join(" "),
+                    entry.ocr_text.clone(),
+                    entry.notes.clone(),
+                    entry.marks.rating.to_string(),
+                    entry.marks.favorite.to_string(),
+                    entry
+                        .marks
+                        .color_label
+                        .map(|label| label.as_str())
+                        .unwrap_or_default()
+                        .to_string(),
+                    entry.width.to_string(),
+                    entry.height.to_string(),
+                    entry.format.clone(),
+                    entry.size_bytes.to_string(),
+                ])?;
+            }
+
+            Ok(Some(("manifest.csv", writer.into_inner()?)))
+        }
+        ManifestFormat::None => Ok(None),
+    }
+}
+
+pub fn export_image(
+    vault: &Vault,
+    image_id: i64,
+    destination: &Path,
+    strip_metadata: bool,
+) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let image = vault.image(image_id)?;
+
+    let source = PathBuf::from(&image.path);
+    let target =
+        image_utils::unique_path(destination, source.file_name().ok_or("Invalid filename")?);
+
+    let bytes = fs::read(&source)?;
+    let bytes = if strip_metadata {
+        metadata_utils::strip_metadata(&bytes)?
+    } else {
+        bytes
+    };
+
+    fs::write(&target, bytes)?;
+
+    Ok(target)
+}
+
+pub fn export_images(
+    vault: &Vault,
+    selection: ExportSelection,
+    destination: &Path,
+    options: &ExportOptions,
+    mut progress: impl FnMut(usize, usize),
+) -> Result<ExportSummary, Box<dyn std::error::Error>> {
+    let conn = vault.read()?;
+
+    let images = match selection {
+        ExportSelection::Ids(ids) => ids
+            .iter()
+            .map(|id| database::get_image(&conn, vault.root(), *id))
+            .collect::<Result<Vec<_>, _>>(),
+        ExportSelection::Query(query) if query.trim().is_empty() => {
+            database::get_images(&conn, vault.root())
+        }
+        ExportSelection::Query(query) => {
+            database::search_images(&conn, vault.root(), &SearchQuery::parse(&query)?)
+        }
+    }?;
+
+    let details = images
+        .into_iter()
+        .map(|image| {
+            let tags = database::get_tags(&conn, image.id)?;
+            let ocr_text = database::get_ocr_text(&conn, image.id)?;
+            let notes = database::get_notes(&conn, image.id)?;
+            let annotations = if options.render_annotations {
+                annotations::get_annotations(&conn, image.id)?
+            } else {
+                Vec::new()
+            };
+            Ok((image, tags, ocr_text, notes, annotations))
+        })
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    drop(conn);
+
+    let mut sink = ExportSink::create(destination, &options.target)?;
+    let (entries, failed) = match write_images(&mut sink, details, options, &mut progress) {
+        Ok(written) => written,
+        Err(e) => {
+            sink.abort();
+            return Err(e);
+        }
+    };
+    sink.finish()?;
+
+    Ok(ExportSummary {
+        destination: destination.to_string_lossy().to_string(),
+        exported: entries.len(),
+        failed,
+    })
+}
+
+fn write_images(
+    sink: &mut ExportSink,
+    details: Vec<ExportDetails>,
+    options: &ExportOptions,
+    progress: &mut impl FnMut(usize, usize),
+) -> Result<(Vec<ManifestEntry>, Vec<ExportFailure>), Box<dyn std::error::Error>> {
+    let total = details.len();
+    let mut entries = Vec::new();
+    let mut failed = Vec::new();
+
+    for (index, (image, tags, ocr_text, notes, annotations)) in details.into_iter().enumerate() {
+        match prepare_image(&image.path, options, &annotations) {
+            Ok(prepared) => {
+                let name = render_filename(
+                    &options.filename_template,
+                    &image,
+                    index + 1,
+                    &prepared.extension,
+                );
+                let exported_as = sink.add_file(&name, &prepared.bytes)?;
+
+                entries.push(ManifestEntry {
+                    id: image.id,
+                    filename: image.filename,
+                    marks: image.marks,
+                    exported_as,
+                    added_at: image.added_at,
+                    tags,
+                    ocr_text,
+                    notes,
+                    width: prepared.width,
+                    height: prepared.height,
+                    format: prepared.format,
+                    size_bytes: prepared.bytes.len(),
+                });
+            }
+            Err(e) => failed.push(ExportFailure {
+                id: image.id,
+                error: e.to_string(),
+            }),
+        }
+
+        progress(index + 1, total);
+    }
+
+    if let Some((name, bytes)) = render_manifest(&entries, &options.manifest)? {
+        sink.add_file(name, &bytes)?;
+    }
+
+    Ok((entries, failed))
+}
@@ -0,0 +1,227 @@
+use std::{
+    collections::HashMap,
+    fs,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::{
+    sync::Semaphore,
+    task::JoinSet,
+    time::{Instant, sleep, sleep_until},
+};
+
+use crate::{
+    Vault,
+    models::{
+        source::ImageSource,
+        url_import::{UrlImportEntry, UrlImportReport, UrlImportStatus},
+    },
+    utils::image_utils::{self, Download, FetchError},
+};
+
+const MAX_CONCURRENT_DOWNLOADS: usize = 4;
+const MAX_ATTEMPTS: u32 = 3;
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+const HOST_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Default)]
+struct HostLimiter(Mutex<HashMap<String, Instant>>);
+
+impl HostLimiter {
+    async fn wait(&self, url: &str) {
+        let host = reqwest::Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_default();
+
+        let slot = {
+            let mut next = self.0.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            let slot = next.get(&host).map_or(now, |at| (*at).max(now));
+            next.insert(host, slot + HOST_INTERVAL);
+            slot
+        };
+
+        sleep_until(slot).await;
+    }
+}
+
+pub fn parse_url_list(content: &str) -> Vec<(usize, String)> {
+    content
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(line, url)| (line, url.to_string()))
+        .collect()
+}
+
+async fn download(vault: &Vault, limiter: &HostLimiter, url: &str) -> Result<Download, String> {
+    let http = vault.http();
+    let image_dir = vault.image_dir();
+
+    let mut attempt = 1;
+    loop {
+        limiter.wait(url).await;
+        match image_utils::save_image_from_url(&http, &image_dir, url.to_string()).await {
+            Ok(download) => return Ok(download),
+            Err(e) => {
+                let retryable = e.downcast_ref::<FetchError>().is_some_and(|e| e.retryable);
+                if !retryable || attempt >= MAX_ATTEMPTS {
+                    return Err(e.to_string());
+                }
+            }
+        }
+
+        sleep(RETRY_DELAY * 2u32.pow(attempt - 1)).await;
+        attempt += 1;
+    }
+}
+
+fn store_download(
+    vault: &Vault,
+    download: &Download,
+    page_url: Option<&str>,
+    batch_id: &str,
+) -> Result<(UrlImportStatus, i64), Box<dyn std::error::Error>> {
+    if let Some(existing) = vault.find_duplicate(&download.path)? {
+        fs::remove_file(&download.path)?;
+        return Ok((UrlImportStatus::Duplicate, existing.id));
+    }
+
+    let image = vault.import_download(download, page_url, Some(batch_id))?;
+    Ok((UrlImportStatus::Imported, image.id))
+}
+
+async fn import_url(
+    vault: Arc<Vault>,
+    limiter: Arc<HostLimiter>,
+    store_lock: Arc<Mutex<()>>,
+    page_url: Option<Arc<str>>,
+    batch_id: Arc<str>,
+    url: &str,
+) -> Result<(UrlImportStatus, i64), String> {
+    let download = download(&vault, &limiter, url).await?;
+
+    tokio::task::spawn_blocking(move || {
+        let _guard = store_lock.lock().unwrap_or_else(|e| e.into_inner());
+        store_download(&vault, &download, page_url.as_deref(), &batch_id).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+pub async fn import_urls(
+    vault: Arc<Vault>,
+    content: &str,
+    progress: impl FnMut(usize, usize),
+) -> UrlImportReport {
+    import_url_list(vault, parse_url_list(content), None, progress).await
+}
+
+pub async fn import_page_images(
+    vault: Arc<Vault>,
+    page_url: &str,
+    image_urls: Vec<String>,
+    progress: impl FnMut(usize, usize),
+) -> UrlImportReport {
+    let urls = image_urls
+        .into_iter()
+        .enumerate()
+        .map(|(index, url)| (index + 1, url))
+        .collect();
+    import_url_list(vault, urls, Some(page_url.into()), progress).await
+}
+
+async fn import_url_list(
+    vault: Arc<Vault>,
+    urls: Vec<(usize, String)>,
+    page_url: Option<Arc<str>>,
+    mut progress: impl FnMut(usize, usize),
+) -> UrlImportReport {
+    let total = urls.len();
+
+    let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_DOWNLOADS));
+    let limiter = Arc::new(HostLimiter::default());
+    let store_lock = Arc::new(Mutex::new(()));
+    let batch_id: Arc<str> = ImageSource::new_batch_id().into();
+
+    let mut entries = Vec::with_capacity(total);
+    let mut first_lines: HashMap<String, usize> = HashMap::new();
+    let mut repeated = Vec::new();
+    let mut pending = HashMap::new();
+    let mut tasks = JoinSet::new();
+
+    for (line, url) in urls {
+        if let Some(first) = first_lines.get(&url) {
+            repeated.push((line, url, *first));
+            continue;
+        }
+        first_lines.insert(url.clone(), line);
+
+        let vault = vault.clone();
+        let semaphore = semaphore.clone();
+        let limiter = limiter.clone();
+        let store_lock = store_lock.clone();
+        let page_url = page_url.clone();
+        let batch_id = batch_id.clone();
+        let task_url = url.clone();
+        let task = tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            import_url(vault, limiter, store_lock, page_url, batch_id, &task_url).await
+        });
+        pending.insert(task.id(), (line, url));
+    }
+
+    let mut completed = repeated.len();
+    while let Some(joined) = tasks.join_next_with_id().await {
+        let (id, result) = match joined {
+            Ok((id, result)) => (id, result),
+            Err(e) => (e.id(), Err(format!("Import task failed: {}", e))),
+        };
+        let Some((line, url)) = pending.remove(&id) else {
+            continue;
+        };
+
+        entries.push(match result {
+            Ok((status, image_id)) => UrlImportEntry {
+                line,
+                url,
+                status,
+                image_id: Some(image_id),
+                detail: (status == UrlImportStatus::Duplicate)
+                    .then(|| "Already in the vault".to_string()),
+            },
+            Err(e) => UrlImportEntry {
+                line,
+                url,
+                status: UrlImportStatus::Failed,
+                image_id: None,
+                detail: Some(e),
+            },
+        });
+
+        completed += 1;
+        progress(completed, total);
+    }
+
+    let image_ids: HashMap<usize, Option<i64>> = entries
+        .iter()
+        .map(|entry| (entry.line, entry.image_id))
+        .collect();
+    entries.extend(
+        repeated
+            .into_iter()
+            .map(|(line, url, first)| UrlImportEntry {
+                line,
+                url,
+                status: UrlImportStatus::Duplicate,
+                image_id: image_ids.get(&first).copied().flatten(),
+                detail: Some(format!("Same URL as line {}", first)),
+            }),
+    );
+    entries.sort_by_key(|entry| entry.line);
+
+    UrlImportReport::new(entries)
+}
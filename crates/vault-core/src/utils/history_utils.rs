@@ -0,0 +1,227 @@
+use crate::{
+    db::{database, history},
+    models::{
+        history::{DeletedImage, HistoryEntry, Operation},
+        image::Image,
+    },
+    utils::path_utils,
+    vault::Vault,
+};
+use rusqlite::Connection;
+use std::{
+    collections::HashSet,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+fn move_file(from: &Path, to: &Path) -> io::Result<()> {
+    fs::rename(from, to).or_else(|_| {
+        fs::copy(from, to)?;
+        fs::remove_file(from)
+    })
+}
+
+fn trash_dir(root: &Path) -> PathBuf {
+    root.join(path_utils::TRASH_DIR)
+}
+
+pub fn trash_record(
+    conn: &Connection,
+    root: &Path,
+    image: &Image,
+) -> Result<DeletedImage, Box<dyn std::error::Error>> {
+    let trash_file = format!("{}-{}", image.id, image.filename);
+    let mut deleted = history::deleted_image(conn, image.id, trash_file)?;
+    deleted.path = path_utils::to_stored_path(root, Path::new(&image.path));
+
+    database::delete_image(conn, image.id)?;
+    Ok(deleted)
+}
+
+pub fn move_to_trash(root: &Path, operation: &Operation) {
+    let Operation::DeleteImages { images } = operation else {
+        return;
+    };
+
+    let trash_dir = trash_dir(root);
+    if let Err(e) = fs::create_dir_all(&trash_dir) {
+        eprintln!("Warning: Could not create {}: {}", trash_dir.display(), e);
+        return;
+    }
+
+    for image in images {
+        let path = path_utils::resolve_stored_path(root, &image.path);
+        if let Err(e) = move_file(Path::new(&path), &trash_dir.join(&image.trash_file)) {
+            eprintln!("Warning: Could not move {} to the trash: {}", path, e);
+        }
+    }
+}
+
+struct Moves(Vec<(PathBuf, PathBuf)>);
+
+impl Moves {
+    fn apply(&mut self, from: PathBuf, to: PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+        if to.exists() {
+            return Err(format!("{} already exists", to.display()).into());
+        }
+        move_file(&from, &to).map_err(|e| format!("Could not move {}: {}", from.display(), e))?;
+        self.0.push((from, to));
+        Ok(())
+    }
+
+    fn revert(self) {
+        for (from, to) in self.0.into_iter().rev() {
+            if let Err(e) = move_file(&to, &from) {
+                eprintln!("Warning: Could not move {} back: {}", to.display(), e);
+            }
+        }
+    }
+}
+
+fn apply(
+    conn: &Connection,
+    root: &Path,
+    operation: &Operation,
+    undo: bool,
+    moves: &mut Moves,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match operation {
+        Operation::Tags { changes, rules } => {
+            for change in changes {
+                let tags = if undo { &change.before } else { &change.after };
+                history::set_image_tags(conn, change.image_id, tags)?;
+            }
+            if let Some((before, after)) = rules {
+                history::set_rules_state(conn, if undo { before } else { after })?;
+            }
+        }
+        Operation::Albums { changes } => {
+            for change in changes {
+                let record = if undo { &change.before } else { &change.after };
+                history::set_album(conn, change.album_id, record.as_ref())?;
+            }
+        }
+        Operation::AlbumFolders { folders, albums } => {
+            history::set_album_folders(conn, if undo { &folders.0 } else { &folders.1 })?;
+            for change in albums {
+                let record = if undo { &change.before } else { &change.after };
+                history::set_album(conn, change.album_id, record.as_ref())?;
+            }
+        }
+        Operation::Marks { changes } => {
+            for change in changes {
+                let marks = if undo { &change.before } else { &change.after };
+                database::set_marks(conn, change.image_id, marks)?;
+            }
+        }
+        Operation::Notes { changes } => {
+            for change in changes {
+                let notes = if undo { &change.before } else { &change.after };
+                database::set_notes(conn, change.image_id, notes)?;
+            }
+        }
+        Operation::Annotations { changes } => {
+            for change in changes {
+                let annotation = if undo { &change.before } else { &change.after };
+                history::set_annotation(conn, change.annotation_id, annotation.as_ref())?;
+            }
+        }
+        Operation::Ocr { changes } => {
+            for change in changes {
+                let text = if undo { &change.before } else { &change.after };
+                database::set_ocr_text(conn, change.image_id, text)?;
+            }
+        }
+        Operation::TagRules { changes } => {
+            for change in changes {
+                let rule = if undo { &change.before } else { &change.after };
+                history::set_tag_rule(conn, change.rule_id, rule.as_ref())?;
+            }
+        }
+        Operation::SmartAlbums { changes } => {
+            for change in changes {
+                let album = if undo { &change.before } else { &change.after };
+                history::set_smart_album(conn, change.smart_album_id, album.as_ref())?;
+            }
+        }
+        Operation::DeleteImages { images } => {
+            let trash_dir = trash_dir(root);
+            fs::create_dir_all(&trash_dir)?;
+
+            for image in images {
+                let path = PathBuf::from(path_utils::resolve_stored_path(root, &image.path));
+                let trash_path = trash_dir.join(&image.trash_file);
+                if undo {
+                    moves.apply(trash_path, path)?;
+                    history::restore_image(conn, image)?;
+                } else {
+                    moves.apply(path, trash_path)?;
+                    database::delete_image(conn, image.id)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn step(vault: &Vault, undo: bool) -> Result<Option<HistoryEntry>, Box<dyn std::error::Error>> {
+    let mut conn = vault.write()?;
+    let tx = conn.transaction()?;
+
+    let next = if undo {
+        history::next_undo(&tx)?
+    } else {
+        history::next_redo(&tx)?
+    };
+    let Some((mut entry, operation)) = next else {
+        return Ok(None);
+    };
+
+    let mut moves = Moves(Vec::new());
+    let result = apply(&tx, vault.root(), &operation, undo, &mut moves)
+        .and_then(|_| Ok(history::set_undone(&tx, entry.id, undo)?))
+        .and_then(|_| Ok(tx.commit()?));
+
+    if let Err(e) = result {
+        moves.revert();
+        return Err(e);
+    }
+
+    entry.undone = undo;
+    Ok(Some(entry))
+}
+
+pub fn undo(vault: &Vault) -> Result<Option<HistoryEntry>, Box<dyn std::error::Error>> {
+    step(vault, true)
+}
+
+pub fn redo(vault: &Vault) -> Result<Option<HistoryEntry>, Box<dyn std::error::Error>> {
+    step(vault, false)
+}
+
+pub fn purge_trash(vault: &Vault) -> Result<(), Box<dyn std::error::Error>> {
+    let trash_dir = trash_dir(vault.root());
+    if !trash_dir.is_dir() {
+        return Ok(());
+    }
+
+    let conn = vault.read()?;
+    let referenced: HashSet<String> = history::get_trash_files(&conn)?.into_iter().collect();
+    drop(conn);
+
+    for entry in fs::read_dir(&trash_dir)?.filter_map(|entry| entry.ok()) {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !referenced.contains(&name)
+            && let Err(e) = fs::remove_file(entry.path())
+        {
+            eprintln!(
+                "Warning: Could not remove {}: {}",
+                entry.path().display(),
+                e
+            );
+        }
+    }
+
+    Ok(())
+}
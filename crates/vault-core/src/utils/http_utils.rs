@@ -0,0 +1,165 @@
+use std::{collections::BTreeMap, fs, time::Duration};
+
+use reqwest::{
+    Certificate, Client, NoProxy, Proxy, Response, StatusCode, Url,
+    header::{COOKIE, HeaderMap, HeaderName, HeaderValue, LOCATION},
+    redirect::Policy,
+};
+
+use crate::{models::network::NetworkConfig, utils::image_utils::FetchError};
+
+const MAX_REDIRECTS: usize = 5;
+const DEFAULT_USER_AGENT: &str = concat!("ImageVault/", env!("CARGO_PKG_VERSION"));
+
+pub struct HttpClient {
+    client: Client,
+    config: NetworkConfig,
+}
+
+impl HttpClient {
+    pub fn new(config: &NetworkConfig) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut builder = Client::builder()
+            .connect_timeout(Duration::from_secs(10))
+            .timeout(Duration::from_secs(60))
+            .pool_idle_timeout(Duration::from_secs(90))
+            .redirect(Policy::none())
+            .user_agent(
+                config
+                    .user_agent
+                    .as_deref()
+                    .filter(|agent| !agent.trim().is_empty())
+                    .unwrap_or(DEFAULT_USER_AGENT),
+            );
+
+        if let Some(proxy) = config
+            .proxy
+            .as_deref()
+            .filter(|proxy| !proxy.trim().is_empty())
+        {
+            let no_proxy = config.no_proxy.as_deref().and_then(NoProxy::from_string);
+            let proxy = Proxy::all(proxy.trim())
+                .map_err(|_| format!("Invalid proxy URL {}", proxy.trim()))?;
+            builder = builder.proxy(proxy.no_proxy(no_proxy));
+        }
+
+        for path in &config.ca_certificates {
+            let pem = fs::read(path)
+                .map_err(|e| format!("Could not read certificate {}: {}", path, e))?;
+            for certificate in Certificate::from_pem_bundle(&pem)? {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+
+        for rule in &config.domain_rules {
+            rule_headers(&rule.headers, &rule.cookies)
+                .map_err(|e| format!("Invalid headers for {}: {}", rule.domain, e))?;
+        }
+
+        Ok(HttpClient {
+            client: builder.build()?,
+            config: config.clone(),
+        })
+    }
+
+    pub fn config(&self) -> &NetworkConfig {
+        &self.config
+    }
+
+    fn headers_for(&self, url: &Url) -> HeaderMap {
+        let Some(host) = url.host_str() else {
+            return HeaderMap::new();
+        };
+
+        self.config
+            .domain_rules
+            .iter()
+            .filter(|rule| domain_matches(host, &rule.domain))
+            .filter_map(|rule| rule_headers(&rule.headers, &rule.cookies).ok())
+            .fold(HeaderMap::new(), |mut headers, rule| {
+                headers.extend(rule);
+                headers
+            })
+    }
+
+    pub async fn get(&self, url: &str) -> Result<Response, Box<dyn std::error::Error>> {
+        let mut current = Url::parse(url)?;
+        let mut redirects = 0;
+
+        let resp = loop {
+            let resp = self
+                .client
+                .get(current.clone())
+                .headers(self.headers_for(&current))
+                .send()
+                .await
+                .map_err(FetchError::retryable)?;
+
+            let Some(location) = redirect_location(&resp) else {
+                break resp;
+            };
+            if redirects == MAX_REDIRECTS {
+                return Err(format!("{} redirected too many times", url).into());
+            }
+            let next = current
+                .join(&location)
+                .map_err(|_| format!("{} redirected to an invalid URL", url))?;
+            if !matches!(next.scheme(), "http" | "https") {
+                return Err(format!("{} redirected to an unsupported URL", url).into());
+            }
+
+            redirects += 1;
+            current = next;
+        };
+
+        let status = resp.status();
+        if !status.is_success() {
+            return Err(FetchError {
+                message: format!("{} returned {}", url, status),
+                retryable: status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS,
+            }
+            .into());
+        }
+
+        Ok(resp)
+    }
+}
+
+fn redirect_location(resp: &Response) -> Option<String> {
+    if !resp.status().is_redirection() {
+        return None;
+    }
+    resp.headers()
+        .get(LOCATION)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+fn domain_matches(host: &str, domain: &str) -> bool {
+    let domain = domain.trim().trim_start_matches("*.").to_lowercase();
+    let host = host.to_lowercase();
+    !domain.is_empty() && (host == domain || host.ends_with(&format!(".{}", domain)))
+}
+
+fn rule_headers(
+    headers: &BTreeMap<String, String>,
+    cookies: &BTreeMap<String, String>,
+) -> Result<HeaderMap, Box<dyn std::error::Error>> {
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+        map.insert(
+            HeaderName::from_bytes(name.trim().as_bytes())?,
+            HeaderValue::from_str(value.trim())?,
+        );
+    }
+
+    if !cookies.is_empty() {
+        let cookie = cookies
+            .iter()
+            .map(|(name, value)| format!("{}={}", name.trim(), value.trim()))
+            .collect::<Vec<_>>()
+            .join("; ");
+        map.insert(COOKIE, HeaderValue::from_str(&cookie)?);
+    }
+
+    Ok(map)
+}
@@ -0,0 +1,332 @@
+#[cfg(feature = "network")]
+use crate::utils::http_utils::HttpClient;
+use regex::Regex;
+use sha2::{Digest, Sha256};
+#[cfg(feature = "ocr")]
+use std::process::Command;
+use std::{
+    ffi::OsStr,
+    fs::{File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+#[cfg(feature = "ocr")]
+pub fn extract_text_from_image(
+    tesseract_path: Option<&Path>,
+    image_path: &str,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let mut command = match tesseract_path {
+        Some(exe_path) => {
+            let tessdata_dir = exe_path
+                .parent()
+                .ok_or("Invalid tesseract path")?
+                .join("tessdata");
+
+            // Convert UNC path to regular Windows path for Tesseract compatibility
+            let tessdata_str = tessdata_dir.to_string_lossy().replace(r"\\?\", "");
+
+            let mut command = Command::new(exe_path);
+            command.env("TESSDATA_PREFIX", tessdata_str);
+            command
+        }
+        None => Command::new("tesseract"),
+    };
+
+    let output = command
+        .arg(image_path)
+        .arg("stdout") // Output to stdout instead of file
+        .output()?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "Tesseract failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        )
+        .into());
+    }
+
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+pub fn unique_file_name(file_name: &str, is_taken: impl Fn(&str) -> bool) -> String {
+    let path = Path::new(file_name);
+
+    // Generate unique filename if target already exists
+    let mut final_name = file_name.to_string();
+    let mut counter = 1;
+
+    while is_taken(&final_name) {
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("image");
+        let extension = path.extension().and_then(|s| s.to_str()).unwrap_or("");
+        final_name = if extension.is_empty() {
+            format!("{} ({})", stem, counter)
+        } else {
+            format!("{} ({}).{}", stem, counter, extension)
+        };
+        counter += 1;
+    }
+
+    final_name
+}
+
+pub fn unique_path(dir: &Path, file_name: &OsStr) -> PathBuf {
+    dir.join(unique_file_name(&file_name.to_string_lossy(), |name| {
+        dir.join(name).exists()
+    }))
+}
+
+pub fn write_unique(dir: &Path, file_name: &OsStr, bytes: &[u8]) -> io::Result<PathBuf> {
+    loop {
+        let path = unique_path(dir, file_name);
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(mut file) => {
+                if let Err(e) = file.write_all(bytes) {
+                    drop(file);
+                    let _ = std::fs::remove_file(&path);
+                    return Err(e);
+                }
+                return Ok(path);
+            }
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+pub fn sanitize_filename(filename: &str) -> String {
+    // Remove invalid characters for Windows and other OSes
+    // Windows: <>:"/\|?* and ASCII control chars (0-31)
+    let re = Regex::new(r#"[<>:"/\\|?*\x00-\x1F]"#).unwrap();
+    re.replace_all(filename, "_").to_string()
+}
+
+pub fn hash_file(path: &Path) -> Result<String, Box<dyn std::error::Error>> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+pub fn image_extension(bytes: &[u8]) -> Result<&'static str, Box<dyn std::error::Error>> {
+    let format = image::guess_format(bytes).map_err(|_| "The data is not a supported image")?;
+    Ok(format.extensions_str().first().copied().unwrap_or("img"))
+}
+
+pub fn decode_data_url(
+    data_url: &str,
+) -> Result<(Vec<u8>, &'static str), Box<dyn std::error::Error>> {
+    use base64::{Engine, engine::general_purpose::STANDARD};
+
+    let (header, data) = data_url
+        .trim()
+        .strip_prefix("data:")
+        .and_then(|rest| rest.split_once(','))
+        .ok_or("The pasted data is not a data URL")?;
+
+    let mut parts = header.split(';');
+    let mime = parts.next().unwrap_or("").trim().to_lowercase();
+    if !mime.is_empty() && !mime.starts_with("image/") {
+        return Err(format!("The pasted data is not an image ({})", mime).into());
+    }
+    if !parts.any(|part| part.trim().eq_ignore_ascii_case("base64")) {
+        return Err("Only base64 data URLs are supported".into());
+    }
+
+    let data: String = data.chars().filter(|c| !c.is_whitespace()).collect();
+    let bytes = STANDARD.decode(data)?;
+    let extension = image_extension(&bytes)?;
+
+    Ok((bytes, extension))
+}
+
+pub fn pasted_filename(extension: &str) -> String {
+    format!("pasted_{}.{}", chrono::Utc::now().timestamp(), extension)
+}
+
+pub fn save_local_image(
+    image_dir: &Path,
+    path: PathBuf,
+    move_image: bool,
+) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let final_path = unique_path(image_dir, path.file_name().ok_or("Invalid filename")?);
+
+    if move_image {
+        std::fs::rename(&path, &final_path)?;
+    } else {
+        std::fs::copy(&path, &final_path)?;
+    }
+
+    Ok(final_path)
+}
+
+#[cfg(feature = "network")]
+const MAX_DOWNLOAD_BYTES: u64 = 50 * 1024 * 1024;
+#[cfg(feature = "network")]
+pub const MAX_PAGE_BYTES: u64 = 5 * 1024 * 1024;
+
+#[cfg(feature = "network")]
+#[derive(Debug)]
+pub struct FetchError {
+    pub message: String,
+    pub retryable: bool,
+}
+
+#[cfg(feature = "network")]
+impl FetchError {
+    pub fn retryable(message: impl ToString) -> Self {
+        FetchError {
+            message: message.to_string(),
+            retryable: true,
+        }
+    }
+}
+
+#[cfg(feature = "network")]
+impl std::fmt::Display for FetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+#[cfg(feature = "network")]
+impl std::error::Error for FetchError {}
+
+#[cfg(feature = "network")]
+pub struct Download {
+    pub path: PathBuf,
+    pub url: String,
+    pub last_modified: Option<String>,
+    pub etag: Option<String>,
+}
+
+#[cfg(feature = "network")]
+fn file_stem_from_url(url: &reqwest::Url) -> String {
+    // Extract filename from URL, fallback to timestamped name if invalid
+    let filename = url
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .unwrap_or("");
+    let stem = Path::new(filename)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .map(sanitize_filename)
+        .unwrap_or_default();
+
+    // If filename is empty or only an extension, fallback to timestamped name
+    if stem.trim().is_empty() || stem.starts_with('.') {
+        return format!("fetched_{}", chrono::Utc::now().timestamp());
+    }
+
+    stem
+}
+
+#[cfg(feature = "network")]
+pub struct Page {
+    pub url: String,
+    pub html: String,
+}
+
+#[cfg(feature = "network")]
+pub enum Fetched {
+    Image(Download),
+    Page(Page),
+}
+
+#[cfg(feature = "network")]
+pub fn content_type(resp: &reqwest::Response) -> Option<String> {
+    resp.headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.split(';').next().unwrap_or("").trim().to_lowercase())
+}
+
+#[cfg(feature = "network")]
+pub fn is_html(mime: &str) -> bool {
+    mime == "text/html" || mime == "application/xhtml+xml"
+}
+
+#[cfg(feature = "network")]
+pub async fn read_body(
+    resp: &mut reqwest::Response,
+    url: &str,
+    limit: u64,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    if resp.content_length().is_some_and(|length| length > limit) {
+        return Err(format!("{} is larger than {} bytes", url, limit).into());
+    }
+
+    let mut bytes = Vec::new();
+    while let Some(chunk) = resp.chunk().await.map_err(FetchError::retryable)? {
+        if (bytes.len() + chunk.len()) as u64 > limit {
+            return Err(format!("{} is larger than {} bytes", url, limit).into());
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(feature = "network")]
+pub async fn fetch_from_url(
+    http: &HttpClient,
+    image_dir: &Path,
+    url: String,
+) -> Result<Fetched, Box<dyn std::error::Error>> {
+    use reqwest::header::{ETAG, HeaderName, LAST_MODIFIED};
+
+    let mut resp = http.get(&url).await?;
+
+    let mime = content_type(&resp);
+    if let Some(mime) = &mime
+        && is_html(mime)
+    {
+        let html = read_body(&mut resp, &url, MAX_PAGE_BYTES).await?;
+        return Ok(Fetched::Page(Page {
+            url: resp.url().to_string(),
+            html: String::from_utf8_lossy(&html).to_string(),
+        }));
+    }
+    if let Some(mime) = mime
+        && !mime.starts_with("image/")
+        && mime != "application/octet-stream"
+    {
+        return Err(format!("{} is not an image ({})", url, mime).into());
+    }
+
+    let header = |name: HeaderName| {
+        resp.headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    let last_modified = header(LAST_MODIFIED);
+    let etag = header(ETAG);
+
+    let bytes = read_body(&mut resp, &url, MAX_DOWNLOAD_BYTES).await?;
+
+    let format = image::guess_format(&bytes)
+        .map_err(|_| format!("{} did not return a supported image", url))?;
+    let extension = format.extensions_str().first().unwrap_or(&"img");
+
+    let filename = format!("{}.{}", file_stem_from_url(resp.url()), extension);
+    let path = write_unique(image_dir, OsStr::new(&filename), &bytes).map_err(|e| e.to_string())?;
+
+    Ok(Fetched::Image(Download {
+        path,
+        url,
+        last_modified,
+        etag,
+    }))
+}
+
+#[cfg(feature = "network")]
+pub async fn save_image_from_url(
+    http: &HttpClient,
+    image_dir: &Path,
+    url: String,
+) -> Result<Download, Box<dyn std::error::Error>> {
+    match fetch_from_url(http, image_dir, url.clone()).await? {
+        Fetched::Image(download) => Ok(download),
+        Fetched::Page(_) => Err(format!("{} is a web page, not an image", url).into()),
+    }
+}
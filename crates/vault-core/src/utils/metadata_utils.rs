@@ -0,0 +1,222 @@
+use crate::models::metadata::{MetadataField, MetadataReport};
+
+use exif::{Context, In, Reader, Tag, experimental::Writer};
+use img_parts::{
+    Bytes, DynImage, ImageEXIF, ImageICC,
+    jpeg::{JpegSegment, markers},
+    webp::CHUNK_XMP,
+};
+use std::{
+    fs::{self, File},
+    io::{Cursor, Write},
+    path::Path,
+};
+
+const XMP_PREFIX: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+const XMP_EXTENSION_PREFIX: &[u8] = b"http://ns.adobe.com/xmp/extension/\0";
+const PNG_XMP_PREFIX: &[u8] = b"XML:com.adobe.xmp\0";
+const XMP_LOCATION: &[u8] = b"exif:GPS";
+const PNG_TEXT_CHUNKS: [[u8; 4]; 4] = [*b"tEXt", *b"zTXt", *b"iTXt", *b"tIME"];
+
+const SENSITIVE_TAGS: &[Tag] = &[
+    Tag::Make,
+    Tag::Model,
+    Tag::Software,
+    Tag::Artist,
+    Tag::Copyright,
+    Tag::ImageDescription,
+    Tag::UserComment,
+    Tag::MakerNote,
+    Tag::DateTime,
+    Tag::DateTimeOriginal,
+    Tag::DateTimeDigitized,
+    Tag::ImageUniqueID,
+    Tag::CameraOwnerName,
+    Tag::BodySerialNumber,
+    Tag::LensMake,
+    Tag::LensModel,
+    Tag::LensSerialNumber,
+];
+
+fn is_sensitive(tag: Tag) -> bool {
+    tag.context() == Context::Gps || SENSITIVE_TAGS.contains(&tag)
+}
+
+fn is_xmp_segment(segment: &JpegSegment) -> bool {
+    segment.marker() == markers::APP1
+        && (segment.contents().starts_with(XMP_PREFIX)
+            || segment.contents().starts_with(XMP_EXTENSION_PREFIX))
+}
+
+fn is_private_segment(segment: &JpegSegment) -> bool {
+    is_xmp_segment(segment)
+        || segment.marker() == markers::APP13
+        || segment.marker() == markers::COM
+}
+
+fn has_xmp(image: &DynImage) -> bool {
+    match image {
+        DynImage::Jpeg(jpeg) => jpeg.segments().iter().any(is_xmp_segment),
+        DynImage::Png(png) => png
+            .chunks_by_type(*b"iTXt")
+            .any(|chunk| chunk.contents().starts_with(PNG_XMP_PREFIX)),
+        DynImage::WebP(webp) => webp.has_chunk(CHUNK_XMP),
+    }
+}
+
+fn has_xmp_location(xmp: &[u8]) -> bool {
+    xmp.windows(XMP_LOCATION.len())
+        .any(|window| window == XMP_LOCATION)
+}
+
+fn strip_xmp_location(image: &mut DynImage) -> bool {
+    match image {
+        DynImage::Jpeg(jpeg) => {
+            let count = jpeg.segments().len();
+            jpeg.segments_mut().retain(|segment| {
+                !(is_xmp_segment(segment) && has_xmp_location(segment.contents()))
+            });
+            jpeg.segments().len() != count
+        }
+        DynImage::Png(png) => {
+            let count = png.chunks().len();
+            png.chunks_mut().retain(|chunk| {
+                !(chunk.kind() == *b"iTXt"
+                    && chunk.contents().starts_with(PNG_XMP_PREFIX)
+                    && has_xmp_location(chunk.contents()))
+            });
+            png.chunks().len() != count
+        }
+        DynImage::WebP(webp) => {
+            let located = webp
+                .chunks_by_id(CHUNK_XMP)
+                .filter_map(|chunk| chunk.content().data())
+                .any(|data| has_xmp_location(data));
+            if located {
+                webp.remove_chunks_by_id(CHUNK_XMP);
+            }
+            located
+        }
+    }
+}
+
+pub fn metadata_report(bytes: &[u8]) -> Result<MetadataReport, Box<dyn std::error::Error>> {
+    let mut fields = Vec::new();
+
+    if let Ok(exif) = Reader::new().read_from_container(&mut Cursor::new(bytes)) {
+        for field in exif.fields() {
+            if matches!(
+                field.tag,
+                Tag::ExifIFDPointer | Tag::GPSInfoIFDPointer | Tag::InteropIFDPointer
+            ) {
+                continue;
+            }
+
+            fields.push(MetadataField {
+                group: format!("{:?}", field.tag.context()),
+                name: field.tag.to_string(),
+                value: field.display_value().with_unit(&exif).to_string(),
+                sensitive: is_sensitive(field.tag),
+            });
+        }
+    }
+
+    let container = DynImage::from_bytes(Bytes::copy_from_slice(bytes))?;
+
+    Ok(MetadataReport {
+        has_location: fields.iter().any(|f| f.group == "Gps"),
+        has_xmp: container.as_ref().is_some_and(has_xmp),
+        has_icc_profile: container
+            .as_ref()
+            .is_some_and(|image| image.icc_profile().is_some()),
+        sensitive_count: fields.iter().filter(|f| f.sensitive).count(),
+        fields,
+    })
+}
+
+pub fn strip_metadata(bytes: &[u8]) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let Some(mut image) = DynImage::from_bytes(Bytes::copy_from_slice(bytes))? else {
+        return reencode(bytes);
+    };
+
+    image.set_exif(None);
+    image.set_icc_profile(None);
+
+    match &mut image {
+        DynImage::Jpeg(jpeg) => jpeg
+            .segments_mut()
+            .retain(|segment| !is_private_segment(segment)),
+        DynImage::Png(png) => png
+            .chunks_mut()
+            .retain(|chunk| !PNG_TEXT_CHUNKS.contains(&chunk.kind())),
+        DynImage::WebP(webp) => webp.remove_chunks_by_id(CHUNK_XMP),
+    }
+
+    Ok(image.encoder().bytes().to_vec())
+}
+
+fn reencode(bytes: &[u8]) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let format = image::guess_format(bytes)?;
+    let img = image::load_from_memory_with_format(bytes, format)?;
+
+    let mut out = Cursor::new(Vec::new());
+    img.write_to(&mut out, format)?;
+
+    Ok(out.into_inner())
+}
+
+pub fn strip_location(bytes: &[u8]) -> Result<Option<Vec<u8>>, Box<dyn std::error::Error>> {
+    let Some(mut image) = DynImage::from_bytes(Bytes::copy_from_slice(bytes))? else {
+        return Ok(None);
+    };
+
+    let mut stripped = strip_xmp_location(&mut image);
+
+    if let Some(raw) = image.exif() {
+        let exif = Reader::new().read_raw(raw.to_vec())?;
+        if exif.fields().any(|f| f.tag.context() == Context::Gps) {
+            let mut writer = Writer::new();
+            for field in exif
+                .fields()
+                .filter(|f| f.ifd_num == In::PRIMARY && f.tag.context() != Context::Gps)
+            {
+                writer.push_field(field);
+            }
+
+            let mut out = Cursor::new(Vec::new());
+            writer.write(&mut out, exif.little_endian())?;
+
+            image.set_exif(Some(Bytes::from(out.into_inner())));
+            stripped = true;
+        }
+    }
+
+    if !stripped {
+        return Ok(None);
+    }
+    Ok(Some(image.encoder().bytes().to_vec()))
+}
+
+pub fn strip_location_in_place(path: &str) -> Result<bool, Box<dyn std::error::Error>> {
+    let path = Path::new(path);
+    let bytes = fs::read(path)?;
+
+    let Some(stripped) = strip_location(&bytes)? else {
+        return Ok(false);
+    };
+
+    let file_name = path.file_name().ok_or("Invalid filename")?;
+    let temp = path.with_file_name(format!(".{}.tmp", file_name.to_string_lossy()));
+    let result = File::create(&temp)
+        .and_then(|mut file| {
+            file.write_all(&stripped)?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&temp, path));
+    if let Err(e) = result {
+        let _ = fs::remove_file(&temp);
+        return Err(e.into());
+    }
+
+    Ok(true)
+}
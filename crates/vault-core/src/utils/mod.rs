@@ -0,0 +1,22 @@
+pub mod annotation_utils;
+pub mod backup_utils;
+pub mod bulk_utils;
+#[cfg(feature = "clipboard")]
+pub mod clipboard_utils;
+pub mod config_utils;
+pub mod export_utils;
+#[cfg(feature = "network")]
+pub mod fetch_utils;
+pub mod history_utils;
+#[cfg(feature = "network")]
+pub mod http_utils;
+pub mod image_utils;
+pub mod metadata_utils;
+#[cfg(feature = "network")]
+pub mod page_utils;
+pub mod path_utils;
+pub mod rule_utils;
+#[cfg(feature = "server")]
+pub mod server_utils;
+pub mod tag_utils;
+pub mod vault_utils;
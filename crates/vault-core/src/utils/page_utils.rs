@@ -0,0 +1,160 @@
+use std::collections::{HashMap, HashSet};
+
+use regex::Regex;
+use reqwest::Url;
+
+use crate::{
+    models::page::{PageImage, PageImageKind, PagePreview},
+    utils::{http_utils::HttpClient, image_utils},
+};
+
+fn decode_entities(value: &str) -> String {
+    value
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+fn parse_attributes(tag: &str) -> HashMap<String, String> {
+    let re =
+        Regex::new(r#"([a-zA-Z_:][-a-zA-Z0-9_:.]*)\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+))"#)
+            .unwrap();
+
+    re.captures_iter(tag)
+        .map(|caps| {
+            let value = caps
+                .get(2)
+                .or_else(|| caps.get(3))
+                .or_else(|| caps.get(4))
+                .map_or("", |value| value.as_str());
+            (caps[1].to_lowercase(), decode_entities(value.trim()))
+        })
+        .collect()
+}
+
+fn largest_srcset_candidate(srcset: &str) -> Option<(String, Option<u32>)> {
+    srcset
+        .split(',')
+        .filter_map(|candidate| {
+            let mut parts = candidate.split_whitespace();
+            let url = parts.next()?;
+            let descriptor = parts.next().unwrap_or("1x");
+            let (size, width) = if let Some(width) = descriptor.strip_suffix('w') {
+                let width = width.parse::<u32>().ok()?;
+                (width as f32, Some(width))
+            } else {
+                let density = descriptor.strip_suffix('x').unwrap_or(descriptor);
+                (density.parse::<f32>().ok()?, None)
+            };
+            Some((url.to_string(), size, width))
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(url, _, width)| (url, width))
+}
+
+pub fn extract_page_images(
+    html: &str,
+    page_url: &str,
+) -> Result<PagePreview, Box<dyn std::error::Error>> {
+    let tag_re = Regex::new(r"(?is)<(meta|img|source|base)\b([^>]*)>").unwrap();
+    let title_re = Regex::new(r"(?is)<title[^>]*>(.*?)</title>").unwrap();
+
+    let mut base = Url::parse(page_url)?;
+    let mut images = Vec::new();
+    let mut seen = HashSet::new();
+
+    let mut push = |base: &Url, url: &str, kind, alt: Option<String>, width| {
+        let Ok(url) = base.join(url) else {
+            return;
+        };
+        if !matches!(url.scheme(), "http" | "https") || !seen.insert(url.to_string()) {
+            return;
+        }
+        images.push(PageImage {
+            url: url.to_string(),
+            kind,
+            alt,
+            width,
+        });
+    };
+
+    for caps in tag_re.captures_iter(html) {
+        let attributes = parse_attributes(&caps[2]);
+        let attribute = |name: &str| {
+            attributes
+                .get(name)
+                .map(String::as_str)
+                .filter(|value| !value.is_empty())
+        };
+
+        match caps[1].to_lowercase().as_str() {
+            "base" => {
+                if let Some(href) = attribute("href").and_then(|href| base.join(href).ok()) {
+                    base = href;
+                }
+            }
+            "meta" => {
+                let name = attribute("property").or_else(|| attribute("name"));
+                let kind = match name {
+                    Some("og:image" | "og:image:url" | "og:image:secure_url") => {
+                        PageImageKind::OpenGraph
+                    }
+                    Some("twitter:image" | "twitter:image:src") => PageImageKind::Twitter,
+                    _ => continue,
+                };
+                if let Some(content) = attribute("content") {
+                    push(&base, content, kind, None, None);
+                }
+            }
+            tag => {
+                let alt = attribute("alt").map(str::to_string);
+                if tag == "img"
+                    && let Some(src) = attribute("src")
+                        .filter(|src| !src.starts_with("data:"))
+                        .or_else(|| attribute("data-src"))
+                {
+                    let width = attribute("width").and_then(|width| width.parse().ok());
+                    push(&base, src, PageImageKind::Img, alt.clone(), width);
+                }
+                if let Some((url, width)) = attribute("srcset")
+                    .or_else(|| attribute("data-srcset"))
+                    .and_then(largest_srcset_candidate)
+                {
+                    push(&base, &url, PageImageKind::Srcset, alt, width);
+                }
+            }
+        }
+    }
+
+    images.sort_by_key(|image| match image.kind {
+        PageImageKind::OpenGraph => 0,
+        PageImageKind::Twitter => 1,
+        _ => 2,
+    });
+
+    Ok(PagePreview {
+        page_url: page_url.to_string(),
+        title: title_re
+            .captures(html)
+            .map(|caps| decode_entities(caps[1].trim()))
+            .filter(|title| !title.is_empty()),
+        images,
+    })
+}
+
+pub async fn preview_page_images(
+    http: &HttpClient,
+    url: &str,
+) -> Result<PagePreview, Box<dyn std::error::Error>> {
+    let mut resp = http.get(url).await?;
+
+    if !image_utils::content_type(&resp).is_some_and(|mime| image_utils::is_html(&mime)) {
+        return Err(format!("{} is not a web page", url).into());
+    }
+
+    let html = image_utils::read_body(&mut resp, url, image_utils::MAX_PAGE_BYTES).await?;
+    extract_page_images(&String::from_utf8_lossy(&html), resp.url().as_str())
+}
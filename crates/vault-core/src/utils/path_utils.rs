@@ -0,0 +1,21 @@
+use std::path::Path;
+
+pub const DB_FILE: &str = "imagevault.db";
+pub const IMAGES_DIR: &str = "Images";
+pub const TRASH_DIR: &str = ".trash";
+
+pub fn to_stored_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string()
+}
+
+pub fn resolve_stored_path(root: &Path, path: &str) -> String {
+    let stored = Path::new(path);
+    if stored.is_absolute() {
+        return path.to_string();
+    }
+
+    root.join(stored).to_string_lossy().to_string()
+}
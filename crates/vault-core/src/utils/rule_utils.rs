@@ -0,0 +1,320 @@
+use crate::{
+    db::{database, history, tags},
+    models::{
+        image::Image,
+        source::{ImageSource, SourceType},
+        tag_rule::{TagRule, TagRuleConditions, TagRuleInput, TagRuleMatch},
+    },
+    utils::tag_utils,
+    vault::Vault,
+};
+use regex::{Regex, RegexBuilder};
+use rusqlite::Connection;
+use std::path::Path;
+
+pub struct RuleSubject<'a> {
+    pub filename: &'a str,
+    pub path: &'a str,
+    pub ocr_text: &'a str,
+    pub source: Option<&'a ImageSource>,
+}
+
+pub struct CompiledRule {
+    conditions: TagRuleConditions,
+    tags: Vec<String>,
+    ocr: Option<Regex>,
+    filename: Option<Regex>,
+}
+
+impl CompiledRule {
+    pub fn new(conditions: &TagRuleConditions, tags: &[String]) -> Result<Self, String> {
+        let ocr = conditions
+            .ocr_pattern
+            .as_deref()
+            .map(|pattern| {
+                RegexBuilder::new(pattern)
+                    .case_insensitive(true)
+                    .build()
+                    .map_err(|e| format!("Invalid OCR pattern: {}", e))
+            })
+            .transpose()?;
+        let filename = conditions
+            .filename_pattern
+            .as_deref()
+            .map(|pattern| {
+                glob_regex(pattern).map_err(|e| format!("Invalid filename pattern: {}", e))
+            })
+            .transpose()?;
+
+        Ok(CompiledRule {
+            conditions: conditions.clone(),
+            tags: tags.to_vec(),
+            ocr,
+            filename,
+        })
+    }
+
+    fn needs_dimensions(&self) -> bool {
+        let c = &self.conditions;
+        c.min_width.is_some()
+            || c.max_width.is_some()
+            || c.min_height.is_some()
+            || c.max_height.is_some()
+    }
+
+    pub fn matches(&self, subject: &RuleSubject, dimensions: Option<(u32, u32)>) -> bool {
+        let c = &self.conditions;
+
+        if let Some(ocr) = &self.ocr
+            && !ocr.is_match(subject.ocr_text)
+        {
+            return false;
+        }
+        if let Some(filename) = &self.filename
+            && !filename.is_match(subject.filename)
+        {
+            return false;
+        }
+        if let Some(format) = &c.format
+            && !extension_matches(subject.filename, format)
+        {
+            return false;
+        }
+        if let Some(folder) = &c.folder
+            && !subject
+                .source
+                .is_some_and(|source| from_folder(source, folder))
+        {
+            return false;
+        }
+        if let Some(domain) = &c.domain
+            && !subject
+                .source
+                .is_some_and(|source| from_domain(source, domain))
+        {
+            return false;
+        }
+
+        if self.needs_dimensions() {
+            let Some((width, height)) = dimensions else {
+                return false;
+            };
+            if c.min_width.is_some_and(|min| width < min)
+                || c.max_width.is_some_and(|max| width > max)
+                || c.min_height.is_some_and(|min| height < min)
+                || c.max_height.is_some_and(|max| height > max)
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+fn glob_regex(pattern: &str) -> Result<Regex, regex::Error> {
+    let pattern = regex::escape(pattern)
+        .replace("\\*", ".*")
+        .replace("\\?", ".");
+    RegexBuilder::new(&format!("^{}$", pattern))
+        .case_insensitive(true)
+        .build()
+}
+
+fn extension_matches(filename: &str, format: &str) -> bool {
+    let Some(extension) = Path::new(filename).extension().and_then(|e| e.to_str()) else {
+        return false;
+    };
+    let normalize = |value: &str| match value.trim_start_matches('.').to_lowercase().as_str() {
+        "jpeg" => "jpg".to_string(),
+        "tiff" => "tif".to_string(),
+        value => value.to_string(),
+    };
+    normalize(extension) == normalize(format)
+}
+
+fn from_folder(source: &ImageSource, folder: &str) -> bool {
+    matches!(source.source_type, SourceType::File | SourceType::Folder)
+        && source
+            .origin
+            .as_deref()
+            .is_some_and(|origin| Path::new(origin).starts_with(folder))
+}
+
+fn url_host(url: &str) -> Option<String> {
+    let (_, rest) = url.split_once("://")?;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let host = authority.rsplit('@').next()?;
+    let host = host.split(':').next()?;
+    Some(host.to_lowercase())
+}
+
+fn from_domain(source: &ImageSource, domain: &str) -> bool {
+    let domain = domain.trim().trim_start_matches("*.").to_lowercase();
+    [&source.origin, &source.page_url]
+        .into_iter()
+        .flatten()
+        .filter_map(|url| url_host(url))
+        .any(|host| host == domain || host.ends_with(&format!(".{}", domain)))
+}
+
+fn normalize_tags(tags: &[String]) -> Vec<String> {
+    tags.iter()
+        .map(|tag| tag_utils::normalize_tag(tag))
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
+pub fn validate_rule(rule: &TagRuleInput) -> Result<TagRuleInput, Box<dyn std::error::Error>> {
+    let c = &rule.conditions;
+    let clean = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+
+    let conditions = TagRuleConditions {
+        ocr_pattern: clean(&c.ocr_pattern),
+        folder: clean(&c.folder),
+        domain: clean(&c.domain),
+        filename_pattern: clean(&c.filename_pattern),
+        format: clean(&c.format),
+        ..c.clone()
+    };
+    let tags = normalize_tags(&rule.tags);
+
+    if rule.name.trim().is_empty() {
+        return Err("Rule name cannot be empty".into());
+    }
+    if tags.is_empty() {
+        return Err("A rule needs at least one tag".into());
+    }
+    let compiled = CompiledRule::new(&conditions, &tags)?;
+    if compiled.ocr.is_none()
+        && compiled.filename.is_none()
+        && conditions.format.is_none()
+        && conditions.folder.is_none()
+        && conditions.domain.is_none()
+        && !compiled.needs_dimensions()
+    {
+        return Err("A rule needs at least one condition".into());
+    }
+
+    Ok(TagRuleInput {
+        name: rule.name.trim().to_string(),
+        enabled: rule.enabled,
+        conditions,
+        tags,
+    })
+}
+
+pub fn load_rules(conn: &Connection) -> Result<Vec<CompiledRule>, Box<dyn std::error::Error>> {
+    Ok(tags::get_rules(conn)?
+        .into_iter()
+        .filter(|rule| rule.enabled)
+        .filter_map(
+            |rule: TagRule| match CompiledRule::new(&rule.conditions, &rule.tags) {
+                Ok(compiled) => Some(compiled),
+                Err(e) => {
+                    eprintln!("Warning: Skipping tag rule {}: {}", rule.name, e);
+                    None
+                }
+            },
+        )
+        .collect())
+}
+
+fn matching_tags(rules: &[CompiledRule], subject: &RuleSubject) -> Vec<String> {
+    let dimensions = rules
+        .iter()
+        .any(CompiledRule::needs_dimensions)
+        .then(|| image::image_dimensions(subject.path).ok())
+        .flatten();
+
+    let mut tags: Vec<String> = rules
+        .iter()
+        .filter(|rule| rule.matches(subject, dimensions))
+        .flat_map(|rule| rule.tags.iter().cloned())
+        .collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+pub fn apply_rules(
+    conn: &Connection,
+    rules: &[CompiledRule],
+    image_id: i64,
+    subject: &RuleSubject,
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let tags = matching_tags(rules, subject);
+    for tag in &tags {
+        database::insert_tag(conn, image_id, tag)?;
+    }
+    Ok(tags)
+}
+
+fn evaluate(
+    conn: &Connection,
+    root: &Path,
+    rules: &[CompiledRule],
+    mut on_match: impl FnMut(&Image, &[String]) -> Result<(), Box<dyn std::error::Error>>,
+) -> Result<Vec<TagRuleMatch>, Box<dyn std::error::Error>> {
+    let mut matches = Vec::new();
+
+    for image in database::get_images(conn, root)? {
+        let ocr_text = database::get_ocr_text(conn, image.id)?;
+        let source = database::get_source(conn, image.id)?;
+        let subject = RuleSubject {
+            filename: &image.filename,
+            path: &image.path,
+            ocr_text: &ocr_text,
+            source: source.as_ref(),
+        };
+        let tags = matching_tags(rules, &subject);
+        if tags.is_empty() {
+            continue;
+        }
+
+        on_match(&image, &tags)?;
+        matches.push(TagRuleMatch {
+            image_id: image.id,
+            filename: image.filename,
+            tags,
+        });
+    }
+
+    Ok(matches)
+}
+
+pub fn preview_rule(
+    vault: &Vault,
+    rule: &TagRuleInput,
+) -> Result<Vec<TagRuleMatch>, Box<dyn std::error::Error>> {
+    let rule = validate_rule(rule)?;
+    let rules = [CompiledRule::new(&rule.conditions, &rule.tags)?];
+
+    let conn = vault.read()?;
+    evaluate(&conn, vault.root(), &rules, |_, _| Ok(()))
+}
+
+pub fn run_rules(vault: &Vault) -> Result<Vec<TagRuleMatch>, Box<dyn std::error::Error>> {
+    let mut conn = vault.write()?;
+    let tx = conn.transaction()?;
+    let rules = load_rules(&tx)?;
+
+    let mut before = Vec::new();
+    let matches = evaluate(&tx, vault.root(), &rules, |image, tags| {
+        before.push((image.id, database::get_tags(&tx, image.id)?));
+        for tag in tags {
+            database::insert_tag(&tx, image.id, tag)?;
+        }
+        Ok(())
+    })?;
+
+    history::record_tags(&tx, "Run tag rules", before, None)?;
+    tx.commit()?;
+    Ok(matches)
+}
@@ -0,0 +1,351 @@
+use std::{
+    net::{Ipv4Addr, SocketAddr},
+    sync::Arc,
+};
+
+use axum::{
+    Json, Router,
+    extract::{DefaultBodyLimit, Multipart, Path, Query, Request, State},
+    http::{StatusCode, header},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{delete, get, post},
+};
+use serde::{Deserialize, Serialize};
+use tokio::{net::TcpListener, sync::oneshot, task::JoinHandle};
+
+use crate::{
+    Vault,
+    models::{
+        image::{Image, ImageDetails},
+        metadata::MetadataReport,
+        source::{ImageSource, SourceType},
+    },
+    utils::image_utils,
+};
+
+const OPENAPI: &str = include_str!("openapi.json");
+const MAX_UPLOAD_BYTES: usize = 100 * 1024 * 1024;
+
+pub trait VaultProvider: Send + Sync + 'static {
+    fn vault(&self) -> Result<Arc<Vault>, String>;
+}
+
+impl VaultProvider for Arc<Vault> {
+    fn vault(&self) -> Result<Arc<Vault>, String> {
+        Ok(self.clone())
+    }
+}
+
+pub struct ApiServer {
+    addr: SocketAddr,
+    shutdown: oneshot::Sender<()>,
+    task: JoinHandle<()>,
+}
+
+impl ApiServer {
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    pub async fn stop(self) {
+        let _ = self.shutdown.send(());
+        let _ = self.task.await;
+    }
+}
+
+#[derive(Clone)]
+struct ApiState {
+    provider: Arc<dyn VaultProvider>,
+    token: Arc<str>,
+}
+
+struct ApiError(StatusCode, String);
+
+impl ApiError {
+    fn bad_request(message: impl ToString) -> Self {
+        ApiError(StatusCode::BAD_REQUEST, message.to_string())
+    }
+}
+
+impl From<Box<dyn std::error::Error>> for ApiError {
+    fn from(e: Box<dyn std::error::Error>) -> Self {
+        match e.downcast_ref::<rusqlite::Error>() {
+            Some(rusqlite::Error::QueryReturnedNoRows) => {
+                ApiError(StatusCode::NOT_FOUND, "Image not found".to_string())
+            }
+            _ => ApiError(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(ErrorBody { error: self.1 })).into_response()
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorBody {
+    error: String,
+}
+
+#[derive(Deserialize)]
+struct SearchQuery {
+    q: String,
+}
+
+#[derive(Deserialize)]
+struct UrlRequest {
+    url: String,
+}
+
+#[derive(Deserialize)]
+struct TagsRequest {
+    tags: Vec<String>,
+}
+
+pub fn generate_token() -> String {
+    format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+pub async fn start(
+    provider: impl VaultProvider,
+    port: u16,
+    token: &str,
+) -> Result<ApiServer, Box<dyn std::error::Error>> {
+    if token.is_empty() {
+        return Err("The API token cannot be empty".into());
+    }
+
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port)).await?;
+    let addr = listener.local_addr()?;
+    let app = router(ApiState {
+        provider: Arc::new(provider),
+        token: token.into(),
+    });
+
+    let (shutdown, shutdown_rx) = oneshot::channel();
+    let task = tokio::spawn(async move {
+        let result = axum::serve(listener, app)
+            .with_graceful_shutdown(async {
+                let _ = shutdown_rx.await;
+            })
+            .await;
+        if let Err(e) = result {
+            eprintln!("Warning: API server stopped: {}", e);
+        }
+    });
+
+    Ok(ApiServer {
+        addr,
+        shutdown,
+        task,
+    })
+}
+
+fn router(state: ApiState) -> Router {
+    let api = Router::new()
+        .route("/images", get(list_images).post(upload_images))
+        .route("/images/search", get(search_images))
+        .route("/images/url", post(fetch_image))
+        .route("/images/{id}", get(get_image))
+        .route("/images/{id}/metadata", get(get_metadata))
+        .route("/images/{id}/tags", get(get_tags).post(add_tags))
+        .route("/images/{id}/tags/{tag}", delete(remove_tag))
+        .route_layer(middleware::from_fn_with_state(state.clone(), authorize))
+        .layer(DefaultBodyLimit::max(MAX_UPLOAD_BYTES));
+
+    Router::new()
+        .route("/openapi.json", get(openapi))
+        .nest("/api", api)
+        .with_state(state)
+}
+
+async fn authorize(State(state): State<ApiState>, request: Request, next: Next) -> Response {
+    let authorized = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|token| tokens_match(token.as_bytes(), state.token.as_bytes()));
+
+    if !authorized {
+        return ApiError(StatusCode::UNAUTHORIZED, "Invalid API token".to_string()).into_response();
+    }
+
+    next.run(request).await
+}
+
+fn tokens_match(given: &[u8], expected: &[u8]) -> bool {
+    given.len() == expected.len()
+        && given
+            .iter()
+            .zip(expected)
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+async fn with_vault<T: Send + 'static>(
+    state: &ApiState,
+    f: impl FnOnce(&Vault) -> Result<T, Box<dyn std::error::Error>> + Send + 'static,
+) -> Result<T, ApiError> {
+    let vault = state
+        .provider
+        .vault()
+        .map_err(|e| ApiError(StatusCode::SERVICE_UNAVAILABLE, e))?;
+
+    tokio::task::spawn_blocking(move || f(&vault).map_err(ApiError::from))
+        .await
+        .map_err(|e| ApiError(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+}
+
+async fn openapi() -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, "application/json")], OPENAPI)
+}
+
+async fn list_images(State(state): State<ApiState>) -> Result<Json<Vec<Image>>, ApiError> {
+    with_vault(&state, |vault| vault.images()).await.map(Json)
+}
+
+async fn search_images(
+    State(state): State<ApiState>,
+    Query(query): Query<SearchQuery>,
+) -> Result<Json<Vec<Image>>, ApiError> {
+    with_vault(&state, move |vault| vault.search(&query.q))
+        .await
+        .map(Json)
+}
+
+async fn get_image(
+    State(state): State<ApiState>,
+    Path(id): Path<i64>,
+) -> Result<Json<ImageDetails>, ApiError> {
+    with_vault(&state, move |vault| vault.image_details(id))
+        .await
+        .map(Json)
+}
+
+async fn get_metadata(
+    State(state): State<ApiState>,
+    Path(id): Path<i64>,
+) -> Result<Json<MetadataReport>, ApiError> {
+    with_vault(&state, move |vault| vault.metadata_report(id))
+        .await
+        .map(Json)
+}
+
+async fn get_tags(
+    State(state): State<ApiState>,
+    Path(id): Path<i64>,
+) -> Result<Json<Vec<String>>, ApiError> {
+    with_vault(&state, move |vault| {
+        vault.image(id)?;
+        vault.tags(id)
+    })
+    .await
+    .map(Json)
+}
+
+async fn add_tags(
+    State(state): State<ApiState>,
+    Path(id): Path<i64>,
+    Json(request): Json<TagsRequest>,
+) -> Result<Json<Vec<String>>, ApiError> {
+    with_vault(&state, move |vault| {
+        vault.image(id)?;
+        for tag in request.tags.iter().map(|tag| tag.trim()) {
+            if !tag.is_empty() {
+                vault.add_tag(id, tag)?;
+            }
+        }
+        vault.tags(id)
+    })
+    .await
+    .map(Json)
+}
+
+async fn remove_tag(
+    State(state): State<ApiState>,
+    Path((id, tag)): Path<(i64, String)>,
+) -> Result<Json<Vec<String>>, ApiError> {
+    with_vault(&state, move |vault| {
+        vault.image(id)?;
+        vault.remove_tag(id, &tag)?;
+        vault.tags(id)
+    })
+    .await
+    .map(Json)
+}
+
+async fn upload_images(
+    State(state): State<ApiState>,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, Json<Vec<Image>>), ApiError> {
+    let mut files = Vec::new();
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(ApiError::bad_request)?
+    {
+        let Some(filename) = field
+            .file_name()
+            .filter(|name| !name.trim().is_empty())
+            .map(image_utils::sanitize_filename)
+        else {
+            continue;
+        };
+        let bytes = field.bytes().await.map_err(ApiError::bad_request)?;
+        if image_utils::image_extension(&bytes).is_err() {
+            return Err(ApiError::bad_request(format!(
+                "{} is not a supported image",
+                filename
+            )));
+        }
+        files.push((filename, bytes));
+    }
+
+    if files.is_empty() {
+        return Err(ApiError::bad_request("No files were uploaded"));
+    }
+
+    let images = with_vault(&state, move |vault| {
+        let batch_id = ImageSource::new_batch_id();
+        files
+            .iter()
+            .map(|(filename, bytes)| {
+                let source = ImageSource::new(SourceType::Api, Some(filename.clone()))
+                    .with_batch(Some(&batch_id));
+                vault.import_bytes(filename, bytes, &source)
+            })
+            .collect()
+    })
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(images)))
+}
+
+async fn fetch_image(
+    State(state): State<ApiState>,
+    Json(request): Json<UrlRequest>,
+) -> Result<(StatusCode, Json<Image>), ApiError> {
+    let vault = state
+        .provider
+        .vault()
+        .map_err(|e| ApiError(StatusCode::SERVICE_UNAVAILABLE, e))?;
+    let (download, page_url) = vault
+        .download_url(&request.url)
+        .await
+        .map_err(|e| ApiError(StatusCode::BAD_GATEWAY, e.to_string()))?;
+
+    let image = with_vault(&state, move |vault| {
+        vault.import_download(&download, page_url.as_deref(), None)
+    })
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(image)))
+}
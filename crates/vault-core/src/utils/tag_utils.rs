@@ -0,0 +1,40 @@
+pub fn normalize_tag(tag: &str) -> String {
+    tag.split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+pub fn is_descendant(tag: &str, ancestor: &str) -> bool {
+    let tag = tag.to_lowercase();
+    let ancestor = ancestor.to_lowercase();
+    tag.strip_prefix(&ancestor)
+        .is_some_and(|rest| rest.starts_with(['/', ':']))
+}
+
+pub fn match_rank(tag: &str, query: &str) -> Option<u8> {
+    let tag = tag.to_lowercase();
+    let query = query.to_lowercase();
+
+    if tag == query {
+        return Some(0);
+    }
+    if tag.starts_with(&query) {
+        return Some(1);
+    }
+    if tag
+        .split(['/', ':'])
+        .any(|segment| segment.starts_with(&query))
+    {
+        return Some(2);
+    }
+    if tag.contains(&query) {
+        return Some(3);
+    }
+
+    let mut chars = tag.chars();
+    query.chars().all(|c| chars.any(|t| t == c)).then_some(4)
+}
@@ -0,0 +1,92 @@
+use crate::{
+    db::database,
+    utils::path_utils,
+    vault::{Vault, VaultConfig},
+};
+
+use rusqlite::MAIN_DB;
+use std::{fs, path::Path};
+
+pub fn copy_dir(from: &Path, to: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    fs::create_dir_all(to)?;
+
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let source = entry.path();
+        let target = to.join(entry.file_name());
+
+        if source.is_dir() {
+            copy_dir(&source, &target)?;
+            continue;
+        }
+
+        let copied = fs::copy(&source, &target)?;
+        if copied != fs::metadata(&source)?.len() {
+            return Err(format!("Incomplete copy of {}", source.display()).into());
+        }
+    }
+
+    Ok(())
+}
+
+pub fn move_vault(vault: &Vault, to: &Path) -> Result<Vault, Box<dyn std::error::Error>> {
+    let from = vault.root();
+    let conn = vault.write()?;
+
+    if to.starts_with(from) {
+        return Err("The new location cannot be inside the current vault".into());
+    }
+    if to.join(path_utils::DB_FILE).exists() {
+        return Err("The new location already contains a vault".into());
+    }
+
+    fs::create_dir_all(to)?;
+    database::relativize_paths(&conn, from)?;
+
+    let result = (|| {
+        conn.backup(MAIN_DB, to.join(path_utils::DB_FILE), None)?;
+
+        for dir in [path_utils::IMAGES_DIR, path_utils::TRASH_DIR] {
+            if from.join(dir).exists() {
+                copy_dir(&from.join(dir), &to.join(dir))?;
+            }
+        }
+
+        Vault::open(VaultConfig {
+            root: to.to_path_buf(),
+            ..vault.config()
+        })
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(to.join(path_utils::DB_FILE));
+        let _ = fs::remove_dir_all(to.join(path_utils::IMAGES_DIR));
+        let _ = fs::remove_dir_all(to.join(path_utils::TRASH_DIR));
+    }
+
+    result
+}
+
+pub fn remove_vault_files(root: &Path) {
+    for path in [
+        root.join(path_utils::DB_FILE),
+        root.join(format!("{}-wal", path_utils::DB_FILE)),
+        root.join(format!("{}-shm", path_utils::DB_FILE)),
+        root.join(path_utils::IMAGES_DIR),
+        root.join(path_utils::TRASH_DIR),
+    ] {
+        if !path.exists() {
+            continue;
+        }
+
+        let result = if path.is_dir() {
+            fs::remove_dir_all(&path)
+        } else {
+            fs::remove_file(&path)
+        };
+
+        if let Err(e) = result {
+            eprintln!("Warning: Could not remove {}: {}", path.display(), e);
+        }
+    }
+}
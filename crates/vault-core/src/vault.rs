@@ -0,0 +1,1369 @@
+use crate::{
+    db::{
+        albums, annotations,
+        database::{self, Db, DbConn},
+        history, tags,
+    },
+    models::{
+        album::{Album, AlbumFolder, AlbumInput, SmartAlbum, SmartAlbumInput},
+        annotation::{Annotation, AnnotationInput},
+        check::CheckReport,
+        history::{HistoryEntry, Operation},
+        image::{ColorLabel, Image, ImageDetails, ImageMarks},
+        metadata::MetadataReport,
+        network::NetworkConfig,
+        search::{ImageSort, MAX_RATING, SearchQuery},
+        settings::Settings,
+        source::{ImageSource, SourceType},
+        tag::{TagAlias, TagImplication, TagUsage},
+        tag_rule::{TagRule, TagRuleInput},
+    },
+    utils::{
+        annotation_utils, history_utils, image_utils, metadata_utils, path_utils,
+        rule_utils::{self, RuleSubject},
+        tag_utils,
+    },
+};
+
+#[cfg(feature = "network")]
+use crate::utils::{http_utils::HttpClient, page_utils};
+#[cfg(feature = "network")]
+use std::sync::{Arc, RwLock};
+
+use std::{
+    collections::HashSet,
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[derive(Clone)]
+pub struct VaultConfig {
+    pub root: PathBuf,
+    pub tesseract_path: Option<PathBuf>,
+    pub network: NetworkConfig,
+}
+
+impl VaultConfig {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            tesseract_path: None,
+            network: NetworkConfig::default(),
+        }
+    }
+}
+
+pub struct Vault {
+    config: VaultConfig,
+    db: Db,
+    #[cfg(feature = "network")]
+    http: RwLock<Arc<HttpClient>>,
+}
+
+impl Vault {
+    pub fn open(config: VaultConfig) -> Result<Self, Box<dyn std::error::Error>> {
+        if !config.root.is_dir() {
+            return Err(format!("Vault location {} is unavailable", config.root.display()).into());
+        }
+        fs::create_dir_all(config.root.join(path_utils::IMAGES_DIR))?;
+
+        let vault = Self::load(config)?;
+        vault.hash_images()?;
+        vault.sync()?;
+        if let Err(e) = history_utils::purge_trash(&vault) {
+            eprintln!("Warning: Could not clean up the trash: {}", e);
+        }
+
+        Ok(vault)
+    }
+
+    pub fn load(config: VaultConfig) -> Result<Self, Box<dyn std::error::Error>> {
+        if !config.root.is_dir() {
+            return Err(format!("Vault location {} is unavailable", config.root.display()).into());
+        }
+
+        let database = config.root.join(path_utils::DB_FILE);
+        let conn = database::open_database(&database)?;
+        database::relativize_paths(&conn, &config.root)?;
+        drop(conn);
+
+        #[cfg(feature = "network")]
+        let http = HttpClient::new(&config.network).or_else(|e| {
+            eprintln!("Warning: Invalid network settings, using defaults: {}", e);
+            HttpClient::new(&NetworkConfig::default())
+        })?;
+
+        Ok(Vault {
+            db: Db::open(&database)?,
+            config,
+            #[cfg(feature = "network")]
+            http: RwLock::new(Arc::new(http)),
+        })
+    }
+
+    pub fn create(config: VaultConfig) -> Result<Self, Box<dyn std::error::Error>> {
+        if config.root.join(path_utils::DB_FILE).exists() {
+            return Err("The location already contains a vault".into());
+        }
+
+        fs::create_dir_all(config.root.join(path_utils::IMAGES_DIR))?;
+        Self::open(config)
+    }
+
+    pub fn config(&self) -> VaultConfig {
+        VaultConfig {
+            #[cfg(feature = "network")]
+            network: self.http().config().clone(),
+            ..self.config.clone()
+        }
+    }
+
+    #[cfg(feature = "network")]
+    pub fn http(&self) -> Arc<HttpClient> {
+        self.http.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    #[cfg(feature = "network")]
+    pub fn set_network(&self, network: &NetworkConfig) -> Result<(), Box<dyn std::error::Error>> {
+        let http = HttpClient::new(network)?;
+        *self.http.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(http);
+        Ok(())
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.config.root
+    }
+
+    pub fn image_dir(&self) -> PathBuf {
+        self.root().join(path_utils::IMAGES_DIR)
+    }
+
+    pub fn read(&self) -> Result<DbConn, Box<dyn std::error::Error>> {
+        self.db.read()
+    }
+
+    pub fn write(&self) -> Result<DbConn, Box<dyn std::error::Error>> {
+        self.db.write()
+    }
+
+    #[cfg(feature = "ocr")]
+    pub fn extract_text(&self, image_path: &str) -> Result<String, Box<dyn std::error::Error>> {
+        image_utils::extract_text_from_image(self.config.tesseract_path.as_deref(), image_path)
+    }
+
+    #[cfg(not(feature = "ocr"))]
+    pub fn extract_text(&self, _image_path: &str) -> Result<String, Box<dyn std::error::Error>> {
+        Err("OCR support is not enabled".into())
+    }
+
+    pub fn add_image(
+        &self,
+        filename: &str,
+        full_path: &str,
+        source: &ImageSource,
+    ) -> Result<i64, Box<dyn std::error::Error>> {
+        if self.settings()?.strip_location_on_ingest
+            && Path::new(full_path).starts_with(self.image_dir())
+            && let Err(e) = metadata_utils::strip_location_in_place(full_path)
+        {
+            eprintln!("Warning: Could not strip location from {}: {}", filename, e);
+        }
+
+        let text = self.extract_text(full_path).unwrap_or_else(|e| {
+            eprintln!("Warning: Could not extract text from {}: {}", filename, e);
+            String::new()
+        });
+
+        let hash = image_utils::hash_file(Path::new(full_path)).ok();
+
+        let conn = self.write()?;
+        let image_id = database::insert_image(
+            &conn,
+            self.root(),
+            filename,
+            full_path,
+            &text,
+            hash.as_deref(),
+        )?;
+        database::insert_source(&conn, image_id, source)?;
+
+        let subject = RuleSubject {
+            filename,
+            path: full_path,
+            ocr_text: &text,
+            source: Some(source),
+        };
+        if let Err(e) = rule_utils::load_rules(&conn)
+            .and_then(|rules| rule_utils::apply_rules(&conn, &rules, image_id, &subject))
+        {
+            eprintln!("Warning: Could not apply tag rules to {}: {}", filename, e);
+        }
+
+        Ok(image_id)
+    }
+
+    pub fn import_file(
+        &self,
+        path: &Path,
+        move_image: bool,
+    ) -> Result<Image, Box<dyn std::error::Error>> {
+        self.import_local(path, move_image, SourceType::File, None)
+    }
+
+    pub fn import_local(
+        &self,
+        path: &Path,
+        move_image: bool,
+        source_type: SourceType,
+        batch_id: Option<&str>,
+    ) -> Result<Image, Box<dyn std::error::Error>> {
+        let source = ImageSource::new(source_type, Some(path.to_string_lossy().to_string()))
+            .with_batch(batch_id);
+        let new_path =
+            image_utils::save_local_image(&self.image_dir(), path.to_path_buf(), move_image)?;
+
+        let image_id = self.add_image(
+            new_path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .ok_or("Invalid filename")?,
+            new_path.to_str().ok_or("Invalid path")?,
+            &source,
+        )?;
+
+        self.image(image_id)
+    }
+
+    // TODO: Make it faster by processing images in parallel
+    pub fn import_folder(
+        &self,
+        path: &Path,
+        move_images: bool,
+        mut progress: impl FnMut(usize, usize),
+    ) -> Result<Vec<Image>, Box<dyn std::error::Error>> {
+        let entries: Vec<_> = fs::read_dir(path)?.collect::<Result<_, _>>()?;
+
+        let total = entries.len();
+        let mut imported = Vec::new();
+        let batch_id = ImageSource::new_batch_id();
+
+        for entry in entries {
+            let entry_path = entry.path();
+            if entry_path.is_dir() {
+                continue;
+            }
+
+            if !entry_path.extension().is_some_and(|ext| {
+                ext == "png" || ext == "jpg" || ext == "jpeg" || ext == "webp" || ext == "gif"
+            }) {
+                continue;
+            }
+
+            imported.push(self.import_local(
+                &entry_path,
+                move_images,
+                SourceType::Folder,
+                Some(&batch_id),
+            )?);
+
+            progress(imported.len(), total);
+        }
+
+        Ok(imported)
+    }
+
+    pub fn import_bytes(
+        &self,
+        filename: &str,
+        bytes: &[u8],
+        source: &ImageSource,
+    ) -> Result<Image, Box<dyn std::error::Error>> {
+        let path = image_utils::write_unique(&self.image_dir(), OsStr::new(filename), bytes)?;
+
+        let image_id = self.add_image(
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .ok_or("Invalid filename")?,
+            path.to_str().ok_or("Invalid path")?,
+            source,
+        )?;
+
+        self.image(image_id)
+    }
+
+    #[cfg(feature = "network")]
+    pub async fn fetch_url(
+        self: &Arc<Self>,
+        url: &str,
+    ) -> Result<Image, Box<dyn std::error::Error>> {
+        let (download, page_url) = self.download_url(url).await?;
+
+        let vault = self.clone();
+        tokio::task::spawn_blocking(move || {
+            vault
+                .import_download(&download, page_url.as_deref(), None)
+                .map_err(|e| e.to_string())
+        })
+        .await?
+        .map_err(Into::into)
+    }
+
+    #[cfg(feature = "network")]
+    pub async fn download_url(
+        &self,
+        url: &str,
+    ) -> Result<(image_utils::Download, Option<String>), Box<dyn std::error::Error>> {
+        let page =
+            match image_utils::fetch_from_url(&self.http(), &self.image_dir(), url.to_string())
+                .await?
+            {
+                image_utils::Fetched::Image(download) => return Ok((download, None)),
+                image_utils::Fetched::Page(page) => page,
+            };
+
+        let preview = page_utils::extract_page_images(&page.html, &page.url)?;
+        let image = preview
+            .images
+            .first()
+            .ok_or_else(|| format!("No images found on {}", page.url))?;
+
+        let download =
+            image_utils::save_image_from_url(&self.http(), &self.image_dir(), image.url.clone())
+                .await?;
+        Ok((download, Some(page.url)))
+    }
+
+    #[cfg(feature = "network")]
+    pub fn import_download(
+        &self,
+        download: &image_utils::Download,
+        page_url: Option<&str>,
+        batch_id: Option<&str>,
+    ) -> Result<Image, Box<dyn std::error::Error>> {
+        let source = ImageSource {
+            page_url: page_url.map(str::to_string),
+            last_modified: download.last_modified.clone(),
+            etag: download.etag.clone(),
+            ..ImageSource::new(SourceType::Url, Some(download.url.clone()))
+        }
+        .with_batch(batch_id);
+
+        let image_id = self.add_image(
+            download
+                .path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or("image"),
+            download.path.to_str().ok_or("Invalid path")?,
+            &source,
+        )?;
+
+        self.image(image_id)
+    }
+
+    pub fn find_duplicate(&self, path: &Path) -> Result<Option<Image>, Box<dyn std::error::Error>> {
+        let hash = image_utils::hash_file(path)?;
+        let conn = self.read()?;
+        Ok(database::find_image_by_hash(&conn, self.root(), &hash)?)
+    }
+
+    pub fn images(&self) -> Result<Vec<Image>, Box<dyn std::error::Error>> {
+        let conn = self.read()?;
+        Ok(database::get_images(&conn, self.root())?)
+    }
+
+    pub fn sorted_images(
+        &self,
+        sort: &ImageSort,
+    ) -> Result<Vec<Image>, Box<dyn std::error::Error>> {
+        let conn = self.read()?;
+        Ok(database::get_sorted_images(&conn, self.root(), sort)?)
+    }
+
+    pub fn image(&self, image_id: i64) -> Result<Image, Box<dyn std::error::Error>> {
+        let conn = self.read()?;
+        Ok(database::get_image(&conn, self.root(), image_id)?)
+    }
+
+    pub fn image_details(&self, image_id: i64) -> Result<ImageDetails, Box<dyn std::error::Error>> {
+        let conn = self.read()?;
+        Ok(ImageDetails {
+            image: database::get_image(&conn, self.root(), image_id)?,
+            tags: database::get_tags(&conn, image_id)?,
+            ocr_text: database::get_ocr_text(&conn, image_id)?,
+            source: database::get_source(&conn, image_id)?,
+            notes: database::get_notes(&conn, image_id)?,
+            annotations: annotations::get_annotations(&conn, image_id)?,
+        })
+    }
+
+    pub fn ocr_text(&self, image_id: i64) -> Result<String, Box<dyn std::error::Error>> {
+        let conn = self.read()?;
+        Ok(database::get_ocr_text(&conn, image_id)?)
+    }
+
+    fn update_marks(
+        &self,
+        image_id: i64,
+        description: &str,
+        update: impl FnOnce(&mut ImageMarks),
+    ) -> Result<Image, Box<dyn std::error::Error>> {
+        let mut conn = self.write()?;
+        let tx = conn.transaction()?;
+
+        let mut marks = database::get_marks(&tx, image_id)?;
+        let before = vec![(image_id, marks.clone())];
+        update(&mut marks);
+        database::set_marks(&tx, image_id, &marks)?;
+        history::record_marks(&tx, description, before)?;
+        tx.commit()?;
+
+        Ok(database::get_image(&conn, self.root(), image_id)?)
+    }
+
+    pub fn set_rating(
+        &self,
+        image_id: i64,
+        rating: u8,
+    ) -> Result<Image, Box<dyn std::error::Error>> {
+        if rating > MAX_RATING {
+            return Err(format!("Rating must be between 0 and {}", MAX_RATING).into());
+        }
+        self.update_marks(image_id, &format!("Rate {} stars", rating), |marks| {
+            marks.rating = rating
+        })
+    }
+
+    pub fn set_favorite(
+        &self,
+        image_id: i64,
+        favorite: bool,
+    ) -> Result<Image, Box<dyn std::error::Error>> {
+        let description = if favorite {
+            "Add to favorites"
+        } else {
+            "Remove from favorites"
+        };
+        self.update_marks(image_id, description, |marks| marks.favorite = favorite)
+    }
+
+    pub fn set_color_label(
+        &self,
+        image_id: i64,
+        color_label: Option<ColorLabel>,
+    ) -> Result<Image, Box<dyn std::error::Error>> {
+        let description = match color_label {
+            Some(label) => format!("Label {}", label.as_str()),
+            None => "Clear label".to_string(),
+        };
+        self.update_marks(image_id, &description, |marks| {
+            marks.color_label = color_label
+        })
+    }
+
+    pub fn notes(&self, image_id: i64) -> Result<String, Box<dyn std::error::Error>> {
+        let conn = self.read()?;
+        Ok(database::get_notes(&conn, image_id)?)
+    }
+
+    pub fn set_notes(&self, image_id: i64, notes: &str) -> Result<(), Box<dyn std::error::Error>> {
+        self.image(image_id)?;
+
+        let mut conn = self.write()?;
+        let tx = conn.transaction()?;
+
+        let before = database::get_notes(&tx, image_id)?;
+        database::set_notes(&tx, image_id, notes.trim())?;
+        history::record_notes(&tx, "Edit notes", image_id, before)?;
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    pub fn annotations(
+        &self,
+        image_id: i64,
+    ) -> Result<Vec<Annotation>, Box<dyn std::error::Error>> {
+        let conn = self.read()?;
+        Ok(annotations::get_annotations(&conn, image_id)?)
+    }
+
+    pub fn add_annotation(
+        &self,
+        image_id: i64,
+        annotation: &AnnotationInput,
+    ) -> Result<Annotation, Box<dyn std::error::Error>> {
+        annotation_utils::validate_annotation(annotation)?;
+        self.image(image_id)?;
+
+        let mut conn = self.write()?;
+        let tx = conn.transaction()?;
+
+        let annotation_id = annotations::insert_annotation(&tx, image_id, annotation)?;
+        history::record_annotation(&tx, "Add annotation", annotation_id, None)?;
+        tx.commit()?;
+
+        Ok(annotations::get_annotation(&conn, annotation_id)?)
+    }
+
+    pub fn update_annotation(
+        &self,
+        annotation_id: i64,
+        annotation: &AnnotationInput,
+    ) -> Result<Annotation, Box<dyn std::error::Error>> {
+        annotation_utils::validate_annotation(annotation)?;
+
+        let mut conn = self.write()?;
+        let tx = conn.transaction()?;
+
+        let before = annotations::get_annotation(&tx, annotation_id)?;
+        annotations::update_annotation(&tx, annotation_id, annotation)?;
+        history::record_annotation(&tx, "Edit annotation", annotation_id, Some(before))?;
+        tx.commit()?;
+
+        Ok(annotations::get_annotation(&conn, annotation_id)?)
+    }
+
+    pub fn delete_annotation(&self, annotation_id: i64) -> Result<(), Box<dyn std::error::Error>> {
+        let mut conn = self.write()?;
+        let tx = conn.transaction()?;
+
+        let before = history::annotation_state(&tx, annotation_id)?;
+        annotations::delete_annotation(&tx, annotation_id)?;
+        history::record_annotation(&tx, "Delete annotation", annotation_id, before)?;
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    pub fn search(&self, query: &str) -> Result<Vec<Image>, Box<dyn std::error::Error>> {
+        let query = SearchQuery::parse(query)?;
+        let conn = self.read()?;
+        Ok(database::search_images(&conn, self.root(), &query)?)
+    }
+
+    pub fn tags(&self, image_id: i64) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let conn = self.read()?;
+        Ok(database::get_tags(&conn, image_id)?)
+    }
+
+    pub fn add_tag(&self, image_id: i64, tag: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut conn = self.write()?;
+        let tx = conn.transaction()?;
+        let before = history::tag_states(&tx, &[image_id])?;
+        database::insert_tag(&tx, image_id, tag)?;
+        history::record_tags(&tx, &format!("Add tag {}", tag), before, None)?;
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    pub fn remove_tag(&self, image_id: i64, tag: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut conn = self.write()?;
+        let tx = conn.transaction()?;
+        let before = history::tag_states(&tx, &[image_id])?;
+        database::remove_tag(&tx, image_id, tag)?;
+        history::record_tags(&tx, &format!("Remove tag {}", tag), before, None)?;
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    fn tag_pair(first: &str, second: &str) -> Result<(String, String), Box<dyn std::error::Error>> {
+        let first = tag_utils::normalize_tag(first);
+        let second = tag_utils::normalize_tag(second);
+        if first.is_empty() || second.is_empty() {
+            return Err("Tag cannot be empty".into());
+        }
+        if first.eq_ignore_ascii_case(&second) {
+            return Err(format!("{} cannot refer to itself", first).into());
+        }
+        Ok((first, second))
+    }
+
+    pub fn tag_aliases(&self) -> Result<Vec<TagAlias>, Box<dyn std::error::Error>> {
+        let conn = self.read()?;
+        Ok(tags::get_aliases(&conn)?)
+    }
+
+    pub fn add_tag_alias(&self, alias: &str, tag: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let (alias, tag) = Self::tag_pair(alias, tag)?;
+
+        let mut conn = self.write()?;
+        let tx = conn.transaction()?;
+        let target = tags::resolve_alias(&tx, &tag)?;
+        if target.eq_ignore_ascii_case(&alias) {
+            return Err(format!("{} is already an alias of {}", tag, alias).into());
+        }
+        if tag_utils::is_descendant(&target, &alias) {
+            return Err(format!("{} cannot be an alias of its own child tag", alias).into());
+        }
+
+        let before = history::tag_states(&tx, &tags::get_tag_tree_images(&tx, &alias)?)?;
+        let rules_before = history::rules_state(&tx)?;
+        tags::insert_alias(&tx, &alias, &tag)?;
+        history::record_tags(
+            &tx,
+            &format!("Alias {} to {}", alias, tag),
+            before,
+            Some(rules_before),
+        )?;
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    pub fn remove_tag_alias(&self, alias: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let alias = tag_utils::normalize_tag(alias);
+
+        let mut conn = self.write()?;
+        let tx = conn.transaction()?;
+        let rules_before = history::rules_state(&tx)?;
+        tags::delete_alias(&tx, &alias)?;
+        history::record_tags(
+            &tx,
+            &format!("Remove alias {}", alias),
+            Vec::new(),
+            Some(rules_before),
+        )?;
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    pub fn tag_implications(&self) -> Result<Vec<TagImplication>, Box<dyn std::error::Error>> {
+        let conn = self.read()?;
+        Ok(tags::get_implications(&conn)?)
+    }
+
+    pub fn add_tag_implication(
+        &self,
+        tag: &str,
+        implied_tag: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let (tag, implied_tag) = Self::tag_pair(tag, implied_tag)?;
+
+        let mut conn = self.write()?;
+        let tx = conn.transaction()?;
+        let rules_before = history::rules_state(&tx)?;
+        tags::insert_implication(&tx, &tag, &implied_tag)?;
+
+        let resolved = tags::resolve_alias(&tx, &tag)?;
+        let image_ids = tags::get_tagged_images(&tx, &resolved)?;
+        let before = history::tag_states(&tx, &image_ids)?;
+        for &image_id in &image_ids {
+            database::insert_tag(&tx, image_id, &resolved)?;
+        }
+
+        history::record_tags(
+            &tx,
+            &format!("Imply {} from {}", implied_tag, tag),
+            before,
+            Some(rules_before),
+        )?;
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    pub fn remove_tag_implication(
+        &self,
+        tag: &str,
+        implied_tag: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let tag = tag_utils::normalize_tag(tag);
+        let implied_tag = tag_utils::normalize_tag(implied_tag);
+
+        let mut conn = self.write()?;
+        let tx = conn.transaction()?;
+        let rules_before = history::rules_state(&tx)?;
+        tags::delete_implication(&tx, &tag, &implied_tag)?;
+        history::record_tags(
+            &tx,
+            &format!("Remove implication {} from {}", implied_tag, tag),
+            Vec::new(),
+            Some(rules_before),
+        )?;
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    pub fn list_tags(&self) -> Result<Vec<TagUsage>, Box<dyn std::error::Error>> {
+        let conn = self.read()?;
+        Ok(tags::get_tag_usage(&conn)?)
+    }
+
+    pub fn autocomplete_tags(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<TagUsage>, Box<dyn std::error::Error>> {
+        let query = tag_utils::normalize_tag(query);
+        let conn = self.read()?;
+        let usage = tags::get_tag_usage(&conn)?;
+        let aliases = tags::get_aliases(&conn)?;
+        drop(conn);
+
+        let mut matches: Vec<(u8, TagUsage)> = usage
+            .into_iter()
+            .filter_map(|usage| {
+                let alias_rank = aliases
+                    .iter()
+                    .filter(|alias| alias.tag.eq_ignore_ascii_case(&usage.tag))
+                    .filter_map(|alias| tag_utils::match_rank(&alias.alias, &query))
+                    .min();
+                let rank = match (tag_utils::match_rank(&usage.tag, &query), alias_rank) {
+                    (Some(rank), Some(alias_rank)) => rank.min(alias_rank),
+                    (rank, alias_rank) => rank.or(alias_rank)?,
+                };
+                Some((rank, usage))
+            })
+            .collect();
+
+        matches.sort_by(|(a_rank, a), (b_rank, b)| {
+            a_rank
+                .cmp(b_rank)
+                .then(b.count.cmp(&a.count))
+                .then(b.last_used.cmp(&a.last_used))
+                .then(a.tag.cmp(&b.tag))
+        });
+
+        Ok(matches
+            .into_iter()
+            .take(limit)
+            .map(|(_, usage)| usage)
+            .collect())
+    }
+
+    pub fn rename_tag(&self, from: &str, to: &str) -> Result<(), Box<dyn std::error::Error>> {
+        self.merge_tags(&[from.to_string()], to)
+    }
+
+    pub fn merge_tags(&self, from: &[String], to: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let to = tag_utils::normalize_tag(to);
+        if to.is_empty() {
+            return Err("Tag cannot be empty".into());
+        }
+
+        let from: Vec<String> = from
+            .iter()
+            .map(|tag| tag_utils::normalize_tag(tag))
+            .filter(|tag| !tag.is_empty() && *tag != to)
+            .collect();
+        if let Some(tag) = from.iter().find(|tag| tag_utils::is_descendant(&to, tag)) {
+            return Err(format!("{} cannot be moved into its own child tag {}", tag, to).into());
+        }
+
+        let mut conn = self.write()?;
+        let tx = conn.transaction()?;
+
+        let mut image_ids = Vec::new();
+        for tag in &from {
+            image_ids.extend(tags::get_tag_tree_images(&tx, tag)?);
+        }
+        image_ids.sort_unstable();
+        image_ids.dedup();
+
+        let before = history::tag_states(&tx, &image_ids)?;
+        let rules_before = history::rules_state(&tx)?;
+        tags::rename_tags(&tx, &from, &to)?;
+        history::record_tags(
+            &tx,
+            &format!("Merge {} into {}", from.join(", "), to),
+            before,
+            Some(rules_before),
+        )?;
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    pub fn unused_tags(&self) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let conn = self.read()?;
+        Ok(tags::get_unused_tags(&conn)?)
+    }
+
+    pub fn tag_rules(&self) -> Result<Vec<TagRule>, Box<dyn std::error::Error>> {
+        let conn = self.read()?;
+        Ok(tags::get_rules(&conn)?)
+    }
+
+    pub fn create_tag_rule(
+        &self,
+        rule: &TagRuleInput,
+    ) -> Result<TagRule, Box<dyn std::error::Error>> {
+        let rule = rule_utils::validate_rule(rule)?;
+
+        let mut conn = self.write()?;
+        let tx = conn.transaction()?;
+
+        let rule_id = tags::insert_rule(&tx, &rule)?;
+        history::record_tag_rule(&tx, &format!("Create rule {}", rule.name), rule_id, None)?;
+        tx.commit()?;
+
+        Ok(tags::get_rule(&conn, rule_id)?)
+    }
+
+    pub fn update_tag_rule(
+        &self,
+        rule_id: i64,
+        rule: &TagRuleInput,
+    ) -> Result<TagRule, Box<dyn std::error::Error>> {
+        let rule = rule_utils::validate_rule(rule)?;
+
+        let mut conn = self.write()?;
+        let tx = conn.transaction()?;
+
+        let before = tags::get_rule(&tx, rule_id)?;
+        tags::update_rule(&tx, rule_id, &rule)?;
+        history::record_tag_rule(
+            &tx,
+            &format!("Edit rule {}", rule.name),
+            rule_id,
+            Some(before),
+        )?;
+        tx.commit()?;
+
+        Ok(tags::get_rule(&conn, rule_id)?)
+    }
+
+    pub fn delete_tag_rule(&self, rule_id: i64) -> Result<(), Box<dyn std::error::Error>> {
+        let mut conn = self.write()?;
+        let tx = conn.transaction()?;
+
+        let before = history::tag_rule_state(&tx, rule_id)?;
+        let description = match &before {
+            Some(rule) => format!("Delete rule {}", rule.name),
+            None => "Delete rule".to_string(),
+        };
+        tags::delete_rule(&tx, rule_id)?;
+        history::record_tag_rule(&tx, &description, rule_id, before)?;
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    pub fn ocr_retry(&self, image_id: i64) -> Result<String, Box<dyn std::error::Error>> {
+        let image = self.image(image_id)?;
+        let text = self.extract_text(&image.path)?;
+
+        let mut conn = self.write()?;
+        let tx = conn.transaction()?;
+
+        let before = history::ocr_states(&tx, &[image_id])?;
+        database::set_ocr_text(&tx, image_id, &text)?;
+        history::record_ocr(&tx, &format!("Re-run OCR on {}", image.filename), before)?;
+        tx.commit()?;
+
+        Ok(text)
+    }
+
+    pub fn delete_image(&self, image_id: i64) -> Result<(), Box<dyn std::error::Error>> {
+        let image = self.image(image_id)?;
+
+        let mut conn = self.write()?;
+        let tx = conn.transaction()?;
+        let deleted = history_utils::trash_record(&tx, self.root(), &image)?;
+        let operation = Operation::DeleteImages {
+            images: vec![deleted],
+        };
+        history::record(&tx, &format!("Delete {}", image.filename), &operation)?;
+        tx.commit()?;
+
+        history_utils::move_to_trash(self.root(), &operation);
+
+        Ok(())
+    }
+
+    pub fn history(&self, limit: usize) -> Result<Vec<HistoryEntry>, Box<dyn std::error::Error>> {
+        let conn = self.read()?;
+        Ok(history::get_history(&conn, limit)?)
+    }
+
+    pub fn undo(&self) -> Result<Option<HistoryEntry>, Box<dyn std::error::Error>> {
+        history_utils::undo(self)
+    }
+
+    pub fn redo(&self) -> Result<Option<HistoryEntry>, Box<dyn std::error::Error>> {
+        history_utils::redo(self)
+    }
+
+    pub fn metadata_report(
+        &self,
+        image_id: i64,
+    ) -> Result<MetadataReport, Box<dyn std::error::Error>> {
+        let bytes = fs::read(self.image(image_id)?.path)?;
+        metadata_utils::metadata_report(&bytes)
+    }
+
+    pub fn settings(&self) -> Result<Settings, Box<dyn std::error::Error>> {
+        let conn = self.read()?;
+        database::get_settings(&conn)
+    }
+
+    pub fn update_settings(&self, settings: &Settings) -> Result<(), Box<dyn std::error::Error>> {
+        let conn = self.write()?;
+        database::save_settings(&conn, settings)
+    }
+
+    pub fn add_sync_folder(
+        &self,
+        path: String,
+        move_images: bool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let conn = self.write()?;
+        database::add_sync_folder(&conn, path, move_images)
+    }
+
+    pub fn albums(&self) -> Result<Vec<Album>, Box<dyn std::error::Error>> {
+        let conn = self.read()?;
+        Ok(albums::get_albums(&conn)?)
+    }
+
+    pub fn album(&self, album_id: i64) -> Result<Album, Box<dyn std::error::Error>> {
+        let conn = self.read()?;
+        Ok(albums::get_album(&conn, album_id)?)
+    }
+
+    pub fn image_albums(&self, image_id: i64) -> Result<Vec<Album>, Box<dyn std::error::Error>> {
+        let conn = self.read()?;
+        Ok(albums::get_image_albums(&conn, image_id)?)
+    }
+
+    fn validate_album(&self, album: &AlbumInput) -> Result<(), Box<dyn std::error::Error>> {
+        if album.name.trim().is_empty() {
+            return Err("Album name cannot be empty".into());
+        }
+        if let Some(cover_image_id) = album.cover_image_id {
+            self.image(cover_image_id)?;
+        }
+        if let Some(folder_id) = album.folder_id {
+            let conn = self.read()?;
+            if !albums::album_folder_exists(&conn, folder_id)? {
+                return Err(format!("Album folder {} does not exist", folder_id).into());
+            }
+        }
+        Ok(())
+    }
+
+    pub fn create_album(&self, album: &AlbumInput) -> Result<Album, Box<dyn std::error::Error>> {
+        self.validate_album(album)?;
+
+        let mut conn = self.write()?;
+        let tx = conn.transaction()?;
+        let album_id = albums::insert_album(&tx, album)?;
+        history::record_albums(
+            &tx,
+            &format!("Create album {}", album.name),
+            vec![(album_id, None)],
+        )?;
+        tx.commit()?;
+
+        Ok(albums::get_album(&conn, album_id)?)
+    }
+
+    pub fn update_album(
+        &self,
+        album_id: i64,
+        album: &AlbumInput,
+    ) -> Result<Album, Box<dyn std::error::Error>> {
+        self.album(album_id)?;
+        self.validate_album(album)?;
+
+        let mut conn = self.write()?;
+        let tx = conn.transaction()?;
+        let before = history::album_states(&tx, &[album_id])?;
+        albums::update_album(&tx, album_id, album)?;
+        history::record_albums(&tx, &format!("Update album {}", album.name), before)?;
+        tx.commit()?;
+
+        Ok(albums::get_album(&conn, album_id)?)
+    }
+
+    pub fn rename_album(
+        &self,
+        album_id: i64,
+        name: &str,
+    ) -> Result<Album, Box<dyn std::error::Error>> {
+        if name.trim().is_empty() {
+            return Err("Album name cannot be empty".into());
+        }
+
+        let mut conn = self.write()?;
+        let tx = conn.transaction()?;
+        let before = history::album_states(&tx, &[album_id])?;
+        albums::rename_album(&tx, album_id, name)?;
+        history::record_albums(&tx, &format!("Rename album to {}", name), before)?;
+        tx.commit()?;
+
+        Ok(albums::get_album(&conn, album_id)?)
+    }
+
+    pub fn delete_album(&self, album_id: i64) -> Result<(), Box<dyn std::error::Error>> {
+        let album = self.album(album_id)?;
+
+        let mut conn = self.write()?;
+        let tx = conn.transaction()?;
+        let before = history::album_states(&tx, &[album_id])?;
+        albums::delete_album(&tx, album_id)?;
+        history::record_albums(&tx, &format!("Delete album {}", album.name), before)?;
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    pub fn album_images(&self, album_id: i64) -> Result<Vec<Image>, Box<dyn std::error::Error>> {
+        let conn = self.read()?;
+        albums::get_album(&conn, album_id)?;
+        Ok(albums::get_album_images(&conn, self.root(), album_id)?)
+    }
+
+    pub fn add_to_album(
+        &self,
+        album_id: i64,
+        image_ids: &[i64],
+    ) -> Result<Album, Box<dyn std::error::Error>> {
+        let mut conn = self.write()?;
+        let album = albums::get_album(&conn, album_id)?;
+        let tx = conn.transaction()?;
+        let before = history::album_states(&tx, &[album_id])?;
+        albums::insert_album_images(&tx, album_id, image_ids)?;
+        history::record_albums(
+            &tx,
+            &format!("Add {} images to {}", image_ids.len(), album.name),
+            before,
+        )?;
+        tx.commit()?;
+        Ok(albums::get_album(&conn, album_id)?)
+    }
+
+    pub fn remove_from_album(
+        &self,
+        album_id: i64,
+        image_ids: &[i64],
+    ) -> Result<Album, Box<dyn std::error::Error>> {
+        let mut conn = self.write()?;
+        let album = albums::get_album(&conn, album_id)?;
+        let tx = conn.transaction()?;
+        let before = history::album_states(&tx, &[album_id])?;
+        albums::delete_album_images(&tx, album_id, image_ids)?;
+        history::record_albums(
+            &tx,
+            &format!("Remove {} images from {}", image_ids.len(), album.name),
+            before,
+        )?;
+        tx.commit()?;
+        Ok(albums::get_album(&conn, album_id)?)
+    }
+
+    pub fn reorder_album(
+        &self,
+        album_id: i64,
+        image_ids: &[i64],
+    ) -> Result<Vec<Image>, Box<dyn std::error::Error>> {
+        let mut conn = self.write()?;
+        let album = albums::get_album(&conn, album_id)?;
+        let tx = conn.transaction()?;
+        let before = history::album_states(&tx, &[album_id])?;
+        albums::reorder_album(&tx, album_id, image_ids)?;
+        history::record_albums(&tx, &format!("Reorder {}", album.name), before)?;
+        tx.commit()?;
+        Ok(albums::get_album_images(&conn, self.root(), album_id)?)
+    }
+
+    pub fn album_folders(&self) -> Result<Vec<AlbumFolder>, Box<dyn std::error::Error>> {
+        let conn = self.read()?;
+        Ok(albums::get_album_folders(&conn)?)
+    }
+
+    pub fn create_album_folder(
+        &self,
+        name: &str,
+        parent_id: Option<i64>,
+    ) -> Result<i64, Box<dyn std::error::Error>> {
+        if name.trim().is_empty() {
+            return Err("Folder name cannot be empty".into());
+        }
+
+        let mut conn = self.write()?;
+        let tx = conn.transaction()?;
+        if let Some(parent_id) = parent_id
+            && !albums::album_folder_exists(&tx, parent_id)?
+        {
+            return Err(format!("Album folder {} does not exist", parent_id).into());
+        }
+
+        let before = albums::get_album_folders(&tx)?;
+        let folder_id = albums::insert_album_folder(&tx, name, parent_id)?;
+        history::record_album_folders(
+            &tx,
+            &format!("Create folder {}", name.trim()),
+            before,
+            Vec::new(),
+        )?;
+        tx.commit()?;
+
+        Ok(folder_id)
+    }
+
+    pub fn rename_album_folder(
+        &self,
+        folder_id: i64,
+        name: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if name.trim().is_empty() {
+            return Err("Folder name cannot be empty".into());
+        }
+
+        let mut conn = self.write()?;
+        let tx = conn.transaction()?;
+        albums::get_folder_parent(&tx, folder_id)?;
+
+        let before = albums::get_album_folders(&tx)?;
+        albums::rename_album_folder(&tx, folder_id, name)?;
+        history::record_album_folders(
+            &tx,
+            &format!("Rename folder to {}", name.trim()),
+            before,
+            Vec::new(),
+        )?;
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    pub fn move_album_folder(
+        &self,
+        folder_id: i64,
+        parent_id: Option<i64>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut conn = self.write()?;
+        let tx = conn.transaction()?;
+        albums::get_folder_parent(&tx, folder_id)?;
+
+        let mut ancestor = parent_id;
+        while let Some(id) = ancestor {
+            if id == folder_id {
+                return Err("A folder cannot be moved into itself".into());
+            }
+            ancestor = albums::get_folder_parent(&tx, id)?;
+        }
+
+        let before = albums::get_album_folders(&tx)?;
+        albums::set_folder_parent(&tx, folder_id, parent_id)?;
+        history::record_album_folders(&tx, "Move folder", before, Vec::new())?;
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    pub fn delete_album_folder(&self, folder_id: i64) -> Result<(), Box<dyn std::error::Error>> {
+        let mut conn = self.write()?;
+        let tx = conn.transaction()?;
+
+        let before = albums::get_album_folders(&tx)?;
+        let albums_before =
+            history::album_states(&tx, &albums::get_folder_album_ids(&tx, folder_id)?)?;
+        albums::delete_album_folder(&tx, folder_id)?;
+        history::record_album_folders(&tx, "Delete folder", before, albums_before)?;
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    pub fn smart_albums(&self) -> Result<Vec<SmartAlbum>, Box<dyn std::error::Error>> {
+        let conn = self.read()?;
+        Ok(albums::get_smart_albums(&conn)?)
+    }
+
+    fn validate_smart_album(album: &SmartAlbumInput) -> Result<(), Box<dyn std::error::Error>> {
+        if album.name.trim().is_empty() {
+            return Err("Smart album name cannot be empty".into());
+        }
+        if SearchQuery::parse(&album.query)?.is_empty() {
+            return Err("Smart album query cannot be empty".into());
+        }
+        Ok(())
+    }
+
+    pub fn create_smart_album(
+        &self,
+        album: &SmartAlbumInput,
+    ) -> Result<SmartAlbum, Box<dyn std::error::Error>> {
+        Self::validate_smart_album(album)?;
+
+        let mut conn = self.write()?;
+        let tx = conn.transaction()?;
+
+        let smart_album_id = albums::insert_smart_album(&tx, album)?;
+        history::record_smart_album(
+            &tx,
+            &format!("Create smart album {}", album.name.trim()),
+            smart_album_id,
+            None,
+        )?;
+        tx.commit()?;
+
+        Ok(albums::get_smart_album(&conn, smart_album_id)?)
+    }
+
+    pub fn update_smart_album(
+        &self,
+        smart_album_id: i64,
+        album: &SmartAlbumInput,
+    ) -> Result<SmartAlbum, Box<dyn std::error::Error>> {
+        Self::validate_smart_album(album)?;
+
+        let mut conn = self.write()?;
+        let tx = conn.transaction()?;
+
+        let before = albums::get_smart_album(&tx, smart_album_id)?;
+        albums::update_smart_album(&tx, smart_album_id, album)?;
+        history::record_smart_album(
+            &tx,
+            &format!("Edit smart album {}", album.name.trim()),
+            smart_album_id,
+            Some(before),
+        )?;
+        tx.commit()?;
+
+        Ok(albums::get_smart_album(&conn, smart_album_id)?)
+    }
+
+    pub fn pin_smart_album(
+        &self,
+        smart_album_id: i64,
+        pinned: bool,
+    ) -> Result<SmartAlbum, Box<dyn std::error::Error>> {
+        let mut conn = self.write()?;
+        let tx = conn.transaction()?;
+
+        let before = albums::get_smart_album(&tx, smart_album_id)?;
+        albums::set_smart_album_pinned(&tx, smart_album_id, pinned)?;
+        let description = if pinned { "Pin" } else { "Unpin" };
+        history::record_smart_album(
+            &tx,
+            &format!("{} smart album {}", description, before.name),
+            smart_album_id,
+            Some(before),
+        )?;
+        tx.commit()?;
+
+        Ok(albums::get_smart_album(&conn, smart_album_id)?)
+    }
+
+    pub fn delete_smart_album(
+        &self,
+        smart_album_id: i64,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut conn = self.write()?;
+        let tx = conn.transaction()?;
+
+        let before = history::smart_album_state(&tx, smart_album_id)?;
+        let description = match &before {
+            Some(album) => format!("Delete smart album {}", album.name),
+            None => "Delete smart album".to_string(),
+        };
+        albums::delete_smart_album(&tx, smart_album_id)?;
+        history::record_smart_album(&tx, &description, smart_album_id, before)?;
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    pub fn smart_album_images(
+        &self,
+        smart_album_id: i64,
+    ) -> Result<Vec<Image>, Box<dyn std::error::Error>> {
+        let album = {
+            let conn = self.read()?;
+            albums::get_smart_album(&conn, smart_album_id)?
+        };
+        self.search(&album.query)
+    }
+
+    pub fn check(&self) -> Result<CheckReport, Box<dyn std::error::Error>> {
+        let conn = self.read()?;
+        let integrity: String = conn.query_row("PRAGMA integrity_check", [], |row| row.get(0))?;
+        let images = database::get_images(&conn, self.root())?;
+        drop(conn);
+
+        let tracked: HashSet<PathBuf> = images
+            .iter()
+            .map(|image| PathBuf::from(&image.path))
+            .collect();
+        let untracked_files = fs::read_dir(self.image_dir())?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file() && !tracked.contains(path))
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
+
+        Ok(CheckReport {
+            integrity,
+            image_count: images.len(),
+            missing_files: images
+                .into_iter()
+                .filter(|image| !Path::new(&image.path).is_file())
+                .collect(),
+            untracked_files,
+        })
+    }
+
+    fn hash_images(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let conn = self.read()?;
+        let images = database::get_unhashed_images(&conn, self.root())?;
+        drop(conn);
+
+        for image in images {
+            let Ok(hash) = image_utils::hash_file(Path::new(&image.path)) else {
+                continue;
+            };
+            let conn = self.write()?;
+            database::set_content_hash(&conn, image.id, &hash)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn sync(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.sync_dir(&self.image_dir())?;
+
+        let conn = self.read()?;
+        let sync_folders = database::get_sync_folders(&conn)?;
+        drop(conn);
+
+        for sync_folder in sync_folders {
+            if let Err(e) = self.sync_dir(Path::new(&sync_folder.path)) {
+                eprintln!("Warning: Could not sync {}: {}", sync_folder.path, e);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn sync_dir(&self, dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            let filename = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or("")
+                .to_string();
+
+            let conn = self.read()?;
+            if database::image_exists(&conn, &filename)? {
+                continue;
+            }
+            drop(conn);
+
+            let full_path = path.to_string_lossy().to_string();
+            let source = ImageSource::new(SourceType::Folder, Some(full_path.clone()));
+            self.add_image(&filename, &full_path, &source)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+use image::{Rgba, RgbaImage};
+use vault_core::{
+    models::annotation::{Annotation, AnnotationKind},
+    utils::annotation_utils,
+};
+
+fn annotation(kind: AnnotationKind, x1: f64, y1: f64, x2: f64, y2: f64) -> Annotation {
+    Annotation {
+        id: 1,
+        image_id: 1,
+        kind,
+        x1,
+        y1,
+        x2,
+        y2,
+        color: "#ff0000".to_string(),
+        text: String::new(),
+        created_at: String::new(),
+    }
+}
+
+#[test]
+fn coordinates_outside_the_image_are_clamped() {
+    let mut img = RgbaImage::new(40, 40);
+    annotation_utils::render_annotations(
+        &mut img,
+        &[
+            annotation(AnnotationKind::Arrow, 1.0, 1.0, 1e15, 1e15),
+            annotation(AnnotationKind::Rect, -5.0, 2.0, 1e300, f64::INFINITY),
+        ],
+    );
+
+    assert_eq!(*img.get_pixel(20, 20), Rgba([255, 0, 0, 255]));
+    assert_eq!(*img.get_pixel(39, 39), Rgba([255, 0, 0, 255]));
+}
@@ -0,0 +1,127 @@
+mod common;
+
+use std::path::Path;
+
+use common::{create_vault, import_png, temp_dir};
+use vault_core::{Vault, VaultConfig, utils::backup_utils};
+
+#[test]
+fn backups_restore_the_library_and_its_files() {
+    let vault = create_vault("backup-round-trip");
+    let first = import_png(&vault, "first.png", [255, 0, 0]);
+    let second = import_png(&vault, "second.png", [0, 255, 0]);
+    vault.add_tag(first.id, "red").unwrap();
+    vault.set_rating(second.id, 4).unwrap();
+    let first_bytes = std::fs::read(&first.path).unwrap();
+
+    let destination = temp_dir("backup-destination");
+    let summary = backup_utils::backup_vault(&vault, &destination, false).unwrap();
+    assert_eq!(summary.file_count, 2);
+
+    vault.delete_image(first.id).unwrap();
+    vault.add_tag(second.id, "green").unwrap();
+    let extra = import_png(&vault, "extra.png", [0, 0, 255]);
+
+    let restored = backup_utils::restore_vault(&vault, Path::new(&summary.path)).unwrap();
+    assert_eq!(restored.images, 2);
+
+    let mut ids: Vec<_> = vault
+        .images()
+        .unwrap()
+        .iter()
+        .map(|image| image.id)
+        .collect();
+    ids.sort_unstable();
+    assert_eq!(ids, vec![first.id, second.id]);
+    assert_eq!(vault.tags(first.id).unwrap(), vec!["red".to_string()]);
+    assert!(vault.tags(second.id).unwrap().is_empty());
+    assert_eq!(vault.image(second.id).unwrap().marks.rating, 4);
+
+    let restored_first = vault.image(first.id).unwrap();
+    assert_eq!(std::fs::read(&restored_first.path).unwrap(), first_bytes);
+    assert!(Path::new(&vault.image(second.id).unwrap().path).exists());
+    assert!(!Path::new(&extra.path).exists());
+
+    let root = vault.root().to_path_buf();
+    drop(vault);
+    let reopened = Vault::open(VaultConfig::new(&root)).unwrap();
+    assert_eq!(reopened.images().unwrap().len(), 2);
+}
+
+fn archives(directory: &Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(directory)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn incremental_backups_restore_files_from_earlier_archives() {
+    let vault = create_vault("backup-incremental");
+    let first = import_png(&vault, "first.png", [255, 0, 0]);
+    let first_bytes = std::fs::read(&first.path).unwrap();
+
+    let destination = temp_dir("backup-incremental-destination");
+    let full = backup_utils::backup_vault(&vault, &destination, false).unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(5));
+
+    let second = import_png(&vault, "second.png", [0, 255, 0]);
+    let second_bytes = std::fs::read(&second.path).unwrap();
+    let incremental = backup_utils::backup_vault(&vault, &destination, true).unwrap();
+    assert!(incremental.incremental);
+    assert_eq!(incremental.file_count, 2);
+    assert_eq!(incremental.new_files, 1);
+
+    let manifest = backup_utils::read_manifest(Path::new(&incremental.path)).unwrap();
+    let full_name = Path::new(&full.path).file_name().unwrap().to_str().unwrap();
+    assert!(manifest.files.iter().any(|file| file.archive == full_name));
+
+    vault.delete_image(first.id).unwrap();
+    vault.delete_image(second.id).unwrap();
+
+    let restored = backup_utils::restore_vault(&vault, Path::new(&incremental.path)).unwrap();
+    assert_eq!(restored.images, 2);
+    assert_eq!(
+        std::fs::read(vault.image(first.id).unwrap().path).unwrap(),
+        first_bytes
+    );
+    assert_eq!(
+        std::fs::read(vault.image(second.id).unwrap().path).unwrap(),
+        second_bytes
+    );
+}
+
+#[test]
+fn failed_backups_leave_no_archive_behind() {
+    let vault = create_vault("backup-failed");
+    let image = import_png(&vault, "photo.png", [255, 0, 0]);
+
+    let destination = temp_dir("backup-failed-destination");
+    let full = backup_utils::backup_vault(&vault, &destination, false).unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(5));
+
+    let missing_snapshot = destination.join("missing.db");
+    let images = vault.images().unwrap();
+    assert!(backup_utils::create_backup(&missing_snapshot, &images, &destination, true).is_err());
+    assert_eq!(archives(&destination).len(), 1);
+
+    std::fs::write(
+        destination.join("imagevault-backup-99999999-000000000.zip"),
+        b"truncated",
+    )
+    .unwrap();
+    assert_eq!(
+        backup_utils::latest_backup(&destination).unwrap().unwrap(),
+        Path::new(&full.path)
+    );
+
+    let incremental = backup_utils::backup_vault(&vault, &destination, true).unwrap();
+    assert!(incremental.incremental);
+    assert_eq!(incremental.new_files, 0);
+
+    vault.delete_image(image.id).unwrap();
+    backup_utils::restore_vault(&vault, Path::new(&incremental.path)).unwrap();
+    assert!(Path::new(&vault.image(image.id).unwrap().path).exists());
+}
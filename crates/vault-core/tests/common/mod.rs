@@ -0,0 +1,138 @@
+#![allow(dead_code)]
+
+use std::{
+    io::{Read, Write},
+    net::TcpListener,
+    path::PathBuf,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread,
+};
+
+use image::{ImageFormat, Rgb, RgbImage};
+use vault_core::{
+    Vault, VaultConfig,
+    models::{
+        image::Image,
+        source::{ImageSource, SourceType},
+    },
+};
+
+static NEXT_DIR: AtomicUsize = AtomicUsize::new(0);
+
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "vault-core-{}-{}-{}",
+        name,
+        std::process::id(),
+        NEXT_DIR.fetch_add(1, Ordering::SeqCst)
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+pub fn create_vault(name: &str) -> Vault {
+    Vault::create(VaultConfig::new(temp_dir(name))).unwrap()
+}
+
+pub fn import_png(vault: &Vault, filename: &str, color: [u8; 3]) -> Image {
+    let source = ImageSource::new(SourceType::Api, None);
+    vault
+        .import_bytes(filename, &png(4, 3, color), &source)
+        .unwrap()
+}
+
+pub fn png(width: u32, height: u32, color: [u8; 3]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    RgbImage::from_pixel(width, height, Rgb(color))
+        .write_to(&mut std::io::Cursor::new(&mut bytes), ImageFormat::Png)
+        .unwrap();
+    bytes
+}
+
+pub fn response(status: &str, headers: &[(&str, &str)], body: &[u8]) -> Vec<u8> {
+    let mut head = format!(
+        "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+        status,
+        body.len()
+    );
+    for (name, value) in headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str("\r\n");
+
+    let mut bytes = head.into_bytes();
+    bytes.extend_from_slice(body);
+    bytes
+}
+
+pub struct Request {
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+pub struct Server {
+    pub port: u16,
+}
+
+impl Server {
+    pub fn start(handler: impl Fn(&Request) -> Vec<u8> + Send + Sync + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let handler = Arc::new(handler);
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                let handler = handler.clone();
+                thread::spawn(move || {
+                    let mut buffer = Vec::new();
+                    let mut chunk = [0; 1024];
+                    while !buffer.windows(4).any(|window| window == b"\r\n\r\n") {
+                        match stream.read(&mut chunk) {
+                            Ok(0) | Err(_) => return,
+                            Ok(read) => buffer.extend_from_slice(&chunk[..read]),
+                        }
+                    }
+
+                    let head = String::from_utf8_lossy(&buffer).to_string();
+                    let mut lines = head.split("\r\n");
+                    let path = lines
+                        .next()
+                        .and_then(|line| line.split(' ').nth(1))
+                        .unwrap_or("/")
+                        .to_string();
+                    let headers = lines
+                        .take_while(|line| !line.is_empty())
+                        .filter_map(|line| line.split_once(':'))
+                        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+                        .collect();
+
+                    let _ = stream.write_all(&handler(&Request { path, headers }));
+                });
+            }
+        });
+
+        Server { port }
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("http://127.0.0.1:{}{}", self.port, path)
+    }
+
+    pub fn localhost_url(&self, path: &str) -> String {
+        format!("http://localhost:{}{}", self.port, path)
+    }
+}
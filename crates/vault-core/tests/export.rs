@@ -0,0 +1,112 @@
+mod common;
+
+use std::{fs::File, io::Read};
+
+use common::{create_vault, import_png, temp_dir};
+use serde_json::Value;
+use vault_core::{
+    models::export::{ExportOptions, ExportSelection, ExportTarget},
+    utils::export_utils,
+};
+use zip::ZipArchive;
+
+#[test]
+fn folder_exports_write_images_and_manifest() {
+    let vault = create_vault("export-folder");
+    let image = import_png(&vault, "photo.png", [255, 0, 0]);
+    vault.add_tag(image.id, "red").unwrap();
+
+    let destination = temp_dir("export-folder-destination");
+    let summary = export_utils::export_images(
+        &vault,
+        ExportSelection::Ids(vec![image.id]),
+        &destination,
+        &ExportOptions::default(),
+        |_, _| {},
+    )
+    .unwrap();
+    assert_eq!(summary.exported, 1);
+    assert!(summary.failed.is_empty());
+
+    assert_eq!(
+        std::fs::read(destination.join("photo.png")).unwrap(),
+        std::fs::read(&image.path).unwrap()
+    );
+    let manifest: Value =
+        serde_json::from_slice(&std::fs::read(destination.join("manifest.json")).unwrap()).unwrap();
+    assert_eq!(manifest[0]["id"], image.id);
+    assert_eq!(manifest[0]["exported_as"], "photo.png");
+    assert_eq!(manifest[0]["tags"], serde_json::json!(["red"]));
+}
+
+#[test]
+fn zip_exports_round_trip_their_manifest() {
+    let vault = create_vault("export-zip");
+    let first = import_png(&vault, "photo.png", [255, 0, 0]);
+    let second = import_png(&vault, "photo.png", [0, 255, 0]);
+    vault.set_notes(second.id, "second").unwrap();
+
+    let destination = temp_dir("export-zip-destination").join("export.zip");
+    let options = ExportOptions {
+        target: ExportTarget::Zip,
+        ..ExportOptions::default()
+    };
+    let summary = export_utils::export_images(
+        &vault,
+        ExportSelection::Ids(vec![first.id, second.id]),
+        &destination,
+        &options,
+        |_, _| {},
+    )
+    .unwrap();
+    assert_eq!(summary.exported, 2);
+    assert!(!destination.with_file_name("export.zip.partial").exists());
+
+    let mut archive = ZipArchive::new(File::open(&destination).unwrap()).unwrap();
+    let mut manifest = String::new();
+    archive
+        .by_name("manifest.json")
+        .unwrap()
+        .read_to_string(&mut manifest)
+        .unwrap();
+    let manifest: Value = serde_json::from_str(&manifest).unwrap();
+    let entries = manifest.as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+
+    for (entry, image) in entries.iter().zip([&first, &second]) {
+        assert_eq!(entry["id"], image.id);
+        let mut bytes = Vec::new();
+        archive
+            .by_name(entry["exported_as"].as_str().unwrap())
+            .unwrap()
+            .read_to_end(&mut bytes)
+            .unwrap();
+        assert_eq!(bytes, std::fs::read(&image.path).unwrap());
+    }
+    assert_ne!(entries[0]["exported_as"], entries[1]["exported_as"]);
+    assert_eq!(entries[1]["notes"], "second");
+}
+
+#[test]
+fn failed_zip_exports_leave_no_partial_archive() {
+    let vault = create_vault("export-zip-failure");
+    let image = import_png(&vault, "photo.png", [255, 0, 0]);
+
+    let destination = temp_dir("export-zip-failure-destination").join("export.zip");
+    std::fs::create_dir_all(destination.join("occupied")).unwrap();
+    let options = ExportOptions {
+        target: ExportTarget::Zip,
+        ..ExportOptions::default()
+    };
+    let result = export_utils::export_images(
+        &vault,
+        ExportSelection::Ids(vec![image.id]),
+        &destination,
+        &options,
+        |_, _| {},
+    );
+
+    assert!(result.is_err());
+    assert!(destination.is_dir());
+    assert!(!destination.with_file_name("export.zip.partial").exists());
+}
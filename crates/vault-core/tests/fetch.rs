@@ -0,0 +1,266 @@
+mod common;
+
+use std::{collections::HashSet, sync::Arc, thread, time::Duration};
+
+use common::{Server, create_vault, png, response, temp_dir};
+use image::{ImageFormat, RgbImage};
+use vault_core::{
+    models::network::NetworkConfig,
+    utils::{
+        fetch_utils,
+        http_utils::HttpClient,
+        image_utils::{self, Download, FetchError, Fetched},
+    },
+};
+
+fn jpeg() -> Vec<u8> {
+    let mut bytes = Vec::new();
+    RgbImage::new(4, 3)
+        .write_to(&mut std::io::Cursor::new(&mut bytes), ImageFormat::Jpeg)
+        .unwrap();
+    bytes
+}
+
+fn fixture_server() -> Server {
+    Server::start(|request| {
+        match request.path.as_str() {
+        "/download/photo.jpeg?size=large" => response(
+            "200 OK",
+            &[("Content-Type", "application/octet-stream")],
+            &png(4, 3, [1, 2, 3]),
+        ),
+        "/untyped/picture" => response("200 OK", &[], &jpeg()),
+        "/gallery/" => response("200 OK", &[("Content-Type", "image/png")], &png(4, 3, [0, 0, 0])),
+        "/notes.txt" => response("200 OK", &[("Content-Type", "text/plain")], b"hello"),
+        "/broken.png" => response(
+            "200 OK",
+            &[("Content-Type", "application/octet-stream")],
+            b"not really a png",
+        ),
+        "/old" => response("301 Moved Permanently", &[("Location", "/images/final.png")], b""),
+        "/images/final.png" => response(
+            "200 OK",
+            &[("Content-Type", "image/png"), ("ETag", "\"v1\"")],
+            &png(4, 3, [5, 5, 5]),
+        ),
+        "/article" => response("302 Found", &[("Location", "/article/")], b""),
+        "/article/" => response(
+            "200 OK",
+            &[("Content-Type", "text/html; charset=utf-8")],
+            b"<html><img src=\"a.png\"></html>",
+        ),
+        "/huge.png" => b"HTTP/1.1 200 OK\r\nContent-Type: image/png\r\nContent-Length: 60000000\r\nConnection: close\r\n\r\n".to_vec(),
+        "/endless.html" => {
+            let mut bytes =
+                b"HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nConnection: close\r\n\r\n".to_vec();
+            bytes.resize(bytes.len() + 6 * 1024 * 1024, b'a');
+            bytes
+        }
+        "/busy.png" => response("503 Service Unavailable", &[], b""),
+        _ => response("404 Not Found", &[], b""),
+    }
+    })
+}
+
+async fn fetch(server: &Server, path: &str) -> Result<Fetched, Box<dyn std::error::Error>> {
+    let http = HttpClient::new(&NetworkConfig::default()).unwrap();
+    image_utils::fetch_from_url(&http, &temp_dir("fetch-fixture"), server.url(path)).await
+}
+
+async fn fetch_image(server: &Server, path: &str) -> Download {
+    match fetch(server, path).await.unwrap() {
+        Fetched::Image(download) => download,
+        Fetched::Page(page) => panic!("{} returned a page", page.url),
+    }
+}
+
+fn file_name(download: &Download) -> &str {
+    download.path.file_name().unwrap().to_str().unwrap()
+}
+
+async fn fetch_error(server: &Server, path: &str) -> String {
+    match fetch(server, path).await {
+        Ok(_) => panic!("{} did not fail", path),
+        Err(e) => e.to_string(),
+    }
+}
+
+#[tokio::test]
+async fn image_type_is_sniffed_from_the_body() {
+    let server = fixture_server();
+
+    let download = fetch_image(&server, "/download/photo.jpeg?size=large").await;
+    assert_eq!(file_name(&download), "photo.png");
+    assert_eq!(download.url, server.url("/download/photo.jpeg?size=large"));
+
+    let download = fetch_image(&server, "/untyped/picture").await;
+    assert_eq!(file_name(&download), "picture.jpg");
+    assert_eq!(std::fs::read(&download.path).unwrap(), jpeg());
+}
+
+#[tokio::test]
+async fn nameless_urls_get_a_generated_name() {
+    let server = fixture_server();
+
+    let download = fetch_image(&server, "/gallery/").await;
+    let name = file_name(&download);
+    assert!(
+        name.starts_with("fetched_") && name.ends_with(".png"),
+        "{}",
+        name
+    );
+}
+
+#[tokio::test]
+async fn non_images_are_rejected() {
+    let server = fixture_server();
+
+    assert!(
+        fetch_error(&server, "/notes.txt")
+            .await
+            .contains("is not an image (text/plain)")
+    );
+    assert!(
+        fetch_error(&server, "/broken.png")
+            .await
+            .contains("did not return a supported image")
+    );
+}
+
+#[tokio::test]
+async fn redirects_name_the_file_after_the_final_url() {
+    let server = fixture_server();
+
+    let download = fetch_image(&server, "/old").await;
+    assert_eq!(file_name(&download), "final.png");
+    assert_eq!(download.url, server.url("/old"));
+    assert_eq!(download.etag.as_deref(), Some("\"v1\""));
+
+    match fetch(&server, "/article").await.unwrap() {
+        Fetched::Page(page) => {
+            assert_eq!(page.url, server.url("/article/"));
+            assert!(page.html.contains("a.png"));
+        }
+        Fetched::Image(download) => panic!("{} is not a page", download.url),
+    }
+}
+
+#[tokio::test]
+async fn oversized_responses_are_rejected() {
+    let server = fixture_server();
+
+    assert!(
+        fetch_error(&server, "/huge.png")
+            .await
+            .contains("is larger than")
+    );
+    assert!(
+        fetch_error(&server, "/endless.html")
+            .await
+            .contains("is larger than")
+    );
+}
+
+#[tokio::test]
+async fn only_server_errors_are_retryable() {
+    let server = fixture_server();
+
+    for (path, retryable) in [("/busy.png", true), ("/missing.png", false)] {
+        let error = fetch(&server, path).await.err().unwrap();
+        let error = error.downcast_ref::<FetchError>().unwrap();
+        assert_eq!(error.retryable, retryable, "{}", path);
+    }
+}
+
+#[test]
+fn image_extension_recognises_supported_formats() {
+    assert_eq!(
+        image_utils::image_extension(&png(1, 1, [0, 0, 0])).unwrap(),
+        "png"
+    );
+    assert_eq!(image_utils::image_extension(&jpeg()).unwrap(), "jpg");
+    assert!(image_utils::image_extension(b"plain text").is_err());
+}
+
+#[tokio::test]
+async fn fetched_pages_import_their_first_image() {
+    let server = Server::start(|request| match request.path.as_str() {
+        "/article/" => response(
+            "200 OK",
+            &[("Content-Type", "text/html")],
+            b"<html><img src=\"photo.png\"></html>",
+        ),
+        "/article/photo.png" => response(
+            "200 OK",
+            &[("Content-Type", "image/png")],
+            &png(4, 3, [9, 9, 9]),
+        ),
+        _ => response("404 Not Found", &[], b""),
+    });
+
+    let vault = Arc::new(create_vault("fetch-page"));
+    let image = vault.fetch_url(&server.url("/article/")).await.unwrap();
+
+    assert_eq!(image.filename, "photo.png");
+    assert_eq!(std::fs::read(&image.path).unwrap(), png(4, 3, [9, 9, 9]));
+    let source = vault.image_details(image.id).unwrap().source.unwrap();
+    assert_eq!(source.page_url, Some(server.url("/article/")));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn concurrent_downloads_with_the_same_name_keep_both_images() {
+    let red = png(4, 3, [255, 0, 0]);
+    let blue = png(4, 3, [0, 0, 255]);
+    let (first, second) = (red.clone(), blue.clone());
+    let server = Server::start(move |request| {
+        thread::sleep(Duration::from_millis(100));
+        let body = if request.path.starts_with("/a/") {
+            &first
+        } else {
+            &second
+        };
+        response("200 OK", &[("Content-Type", "image/png")], body)
+    });
+
+    let vault = Arc::new(create_vault("fetch-race"));
+    let content = format!(
+        "{}\n{}\n",
+        server.url("/a/image.png"),
+        server.localhost_url("/b/image.png")
+    );
+    let report = fetch_utils::import_urls(vault.clone(), &content, |_, _| {}).await;
+
+    assert_eq!(report.imported, 2);
+    assert_eq!(report.duplicates, 0);
+
+    let images = vault.images().unwrap();
+    assert_eq!(images.len(), 2);
+    let mut contents = HashSet::new();
+    for image in &images {
+        contents.insert(std::fs::read(&image.path).unwrap());
+    }
+    assert!(contents.contains(&red));
+    assert!(contents.contains(&blue));
+}
+
+#[test]
+fn write_unique_never_reuses_a_name() {
+    let dir = temp_dir("write-unique");
+    let handles: Vec<_> = (0..16u8)
+        .map(|index| {
+            let dir = dir.clone();
+            thread::spawn(move || {
+                image_utils::write_unique(&dir, "image.png".as_ref(), &[index]).unwrap()
+            })
+        })
+        .collect();
+
+    let paths: HashSet<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    assert_eq!(paths.len(), 16);
+
+    let contents: HashSet<_> = paths
+        .iter()
+        .map(|path| std::fs::read(path).unwrap())
+        .collect();
+    assert_eq!(contents.len(), 16);
+}
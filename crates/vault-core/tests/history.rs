@@ -0,0 +1,429 @@
+mod common;
+
+use std::path::Path;
+
+use common::{create_vault, import_png, temp_dir};
+use vault_core::{
+    Vault, VaultConfig,
+    models::{
+        album::{AlbumInput, SmartAlbumInput},
+        annotation::AnnotationInput,
+        bulk::BulkSelection,
+        tag_rule::{TagRuleConditions, TagRuleInput},
+    },
+    utils::{bulk_utils, rule_utils},
+};
+
+fn annotation_texts(vault: &Vault, image_id: i64) -> Vec<String> {
+    vault
+        .annotations(image_id)
+        .unwrap()
+        .into_iter()
+        .map(|annotation| annotation.text)
+        .collect()
+}
+
+fn aliases(vault: &Vault) -> Vec<(String, String)> {
+    vault
+        .tag_aliases()
+        .unwrap()
+        .into_iter()
+        .map(|alias| (alias.alias, alias.tag))
+        .collect()
+}
+
+fn implications(vault: &Vault) -> Vec<(String, String)> {
+    vault
+        .tag_implications()
+        .unwrap()
+        .into_iter()
+        .map(|implication| (implication.tag, implication.implied_tag))
+        .collect()
+}
+
+fn rule_names(vault: &Vault) -> Vec<String> {
+    vault
+        .tag_rules()
+        .unwrap()
+        .into_iter()
+        .map(|rule| rule.name)
+        .collect()
+}
+
+fn smart_albums(vault: &Vault) -> Vec<(String, String, bool)> {
+    vault
+        .smart_albums()
+        .unwrap()
+        .into_iter()
+        .map(|album| (album.name, album.query, album.pinned))
+        .collect()
+}
+
+fn folders(vault: &Vault) -> Vec<(i64, String, Option<i64>)> {
+    vault
+        .album_folders()
+        .unwrap()
+        .into_iter()
+        .map(|folder| (folder.id, folder.name, folder.parent_id))
+        .collect()
+}
+
+#[test]
+fn deleted_images_round_trip_through_undo_and_redo() {
+    let vault = create_vault("history-delete");
+    let image = import_png(&vault, "photo.png", [10, 20, 30]);
+    vault.add_tag(image.id, "cat").unwrap();
+
+    vault.delete_image(image.id).unwrap();
+    let trash_file = vault
+        .root()
+        .join(".trash")
+        .join(format!("{}-photo.png", image.id));
+    assert!(!Path::new(&image.path).exists());
+    assert!(trash_file.exists());
+    assert!(vault.images().unwrap().is_empty());
+
+    vault.undo().unwrap().unwrap();
+    assert!(Path::new(&image.path).exists());
+    assert!(!trash_file.exists());
+    assert_eq!(vault.tags(image.id).unwrap(), vec!["cat".to_string()]);
+
+    vault.redo().unwrap().unwrap();
+    assert!(trash_file.exists());
+    assert!(vault.images().unwrap().is_empty());
+}
+
+#[test]
+fn purge_keeps_trash_files_of_live_images() {
+    let vault = create_vault("history-purge");
+    let image = import_png(&vault, "photo.png", [10, 20, 30]);
+    let root = vault.root().to_path_buf();
+    drop(vault);
+
+    let trash_dir = root.join(".trash");
+    std::fs::create_dir_all(&trash_dir).unwrap();
+    let live = trash_dir.join(format!("{}-photo.png", image.id));
+    let orphan = trash_dir.join("999-orphan.png");
+    std::fs::write(&live, b"live").unwrap();
+    std::fs::write(&orphan, b"orphan").unwrap();
+
+    Vault::open(VaultConfig::new(&root)).unwrap();
+    assert!(live.exists());
+    assert!(!orphan.exists());
+}
+
+#[test]
+fn tag_aliases_round_trip_through_undo_and_redo() {
+    let vault = create_vault("history-alias");
+    let image = import_png(&vault, "photo.png", [10, 20, 30]);
+    vault.add_tag(image.id, "kitty").unwrap();
+
+    vault.add_tag_alias("kitty", "cat").unwrap();
+    assert_eq!(vault.tags(image.id).unwrap(), vec!["cat".to_string()]);
+    assert_eq!(aliases(&vault), vec![("kitty".into(), "cat".into())]);
+
+    vault.undo().unwrap().unwrap();
+    assert_eq!(vault.tags(image.id).unwrap(), vec!["kitty".to_string()]);
+    assert!(aliases(&vault).is_empty());
+
+    vault.redo().unwrap().unwrap();
+    assert_eq!(vault.tags(image.id).unwrap(), vec!["cat".to_string()]);
+    assert_eq!(aliases(&vault), vec![("kitty".into(), "cat".into())]);
+
+    vault.remove_tag_alias("kitty").unwrap();
+    assert!(aliases(&vault).is_empty());
+    vault.undo().unwrap().unwrap();
+    assert_eq!(aliases(&vault), vec![("kitty".into(), "cat".into())]);
+}
+
+#[test]
+fn tag_implications_round_trip_through_undo_and_redo() {
+    let vault = create_vault("history-implication");
+    let image = import_png(&vault, "photo.png", [10, 20, 30]);
+    vault.add_tag(image.id, "cat").unwrap();
+
+    vault.add_tag_implication("cat", "animal").unwrap();
+    assert_eq!(
+        vault.tags(image.id).unwrap(),
+        vec!["animal".to_string(), "cat".to_string()]
+    );
+
+    vault.undo().unwrap().unwrap();
+    assert_eq!(vault.tags(image.id).unwrap(), vec!["cat".to_string()]);
+    assert!(implications(&vault).is_empty());
+
+    vault.redo().unwrap().unwrap();
+    assert_eq!(
+        vault.tags(image.id).unwrap(),
+        vec!["animal".to_string(), "cat".to_string()]
+    );
+    assert_eq!(implications(&vault), vec![("cat".into(), "animal".into())]);
+
+    vault.remove_tag_implication("cat", "animal").unwrap();
+    assert!(implications(&vault).is_empty());
+    vault.undo().unwrap().unwrap();
+    assert_eq!(implications(&vault), vec![("cat".into(), "animal".into())]);
+}
+
+#[test]
+fn rule_runs_round_trip_through_undo_and_redo() {
+    let vault = create_vault("history-rules");
+    let image = import_png(&vault, "screenshot.png", [10, 20, 30]);
+    vault
+        .create_tag_rule(&TagRuleInput {
+            name: "Screenshots".into(),
+            conditions: TagRuleConditions {
+                filename_pattern: Some("screenshot*".into()),
+                ..Default::default()
+            },
+            tags: vec!["screenshot".into()],
+            ..Default::default()
+        })
+        .unwrap();
+
+    rule_utils::run_rules(&vault).unwrap();
+    assert_eq!(
+        vault.tags(image.id).unwrap(),
+        vec!["screenshot".to_string()]
+    );
+
+    vault.undo().unwrap().unwrap();
+    assert!(vault.tags(image.id).unwrap().is_empty());
+
+    vault.redo().unwrap().unwrap();
+    assert_eq!(
+        vault.tags(image.id).unwrap(),
+        vec!["screenshot".to_string()]
+    );
+}
+
+#[test]
+fn album_folders_round_trip_through_undo_and_redo() {
+    let vault = create_vault("history-folders");
+    let parent = vault.create_album_folder("Trips", None).unwrap();
+    let child = vault.create_album_folder("2024", Some(parent)).unwrap();
+    let album = vault
+        .create_album(&AlbumInput {
+            name: "Rome".into(),
+            folder_id: Some(child),
+            ..Default::default()
+        })
+        .unwrap();
+
+    vault.rename_album_folder(child, "2025").unwrap();
+    vault.undo().unwrap().unwrap();
+    assert_eq!(
+        folders(&vault),
+        vec![
+            (child, "2024".into(), Some(parent)),
+            (parent, "Trips".into(), None)
+        ]
+    );
+
+    vault.move_album_folder(child, None).unwrap();
+    vault.undo().unwrap().unwrap();
+    assert_eq!(folders(&vault)[0], (child, "2024".into(), Some(parent)));
+
+    let before = folders(&vault);
+    vault.delete_album_folder(child).unwrap();
+    assert_eq!(vault.album(album.id).unwrap().folder_id, Some(parent));
+
+    vault.undo().unwrap().unwrap();
+    assert_eq!(folders(&vault), before);
+    assert_eq!(vault.album(album.id).unwrap().folder_id, Some(child));
+
+    vault.redo().unwrap().unwrap();
+    assert_eq!(folders(&vault), vec![(parent, "Trips".into(), None)]);
+    assert_eq!(vault.album(album.id).unwrap().folder_id, Some(parent));
+
+    for _ in 0..3 {
+        vault.undo().unwrap().unwrap();
+    }
+    assert_eq!(folders(&vault), vec![(parent, "Trips".into(), None)]);
+}
+
+#[test]
+fn bulk_deletes_move_files_after_commit_and_undo_together() {
+    let vault = create_vault("history-bulk-delete");
+    let first = import_png(&vault, "first.png", [10, 20, 30]);
+    let second = import_png(&vault, "second.png", [30, 20, 10]);
+
+    let results =
+        bulk_utils::delete_images(&vault, BulkSelection::Ids(vec![first.id, second.id, 999]))
+            .unwrap();
+    assert_eq!(results.iter().filter(|result| result.ok).count(), 2);
+    assert!(vault.images().unwrap().is_empty());
+    for image in [&first, &second] {
+        assert!(!Path::new(&image.path).exists());
+        assert!(
+            vault
+                .root()
+                .join(".trash")
+                .join(format!("{}-{}", image.id, image.filename))
+                .exists()
+        );
+    }
+
+    vault.undo().unwrap().unwrap();
+    assert_eq!(vault.images().unwrap().len(), 2);
+    assert!(Path::new(&first.path).exists());
+    assert!(Path::new(&second.path).exists());
+}
+
+#[test]
+fn notes_round_trip_through_undo_and_redo() {
+    let vault = create_vault("history-notes");
+    let image = import_png(&vault, "photo.png", [10, 20, 30]);
+
+    vault.set_notes(image.id, "first").unwrap();
+    vault.set_notes(image.id, "second").unwrap();
+    vault.set_notes(image.id, "second").unwrap();
+
+    vault.undo().unwrap().unwrap();
+    assert_eq!(vault.notes(image.id).unwrap(), "first");
+    vault.undo().unwrap().unwrap();
+    assert_eq!(vault.notes(image.id).unwrap(), "");
+    assert!(vault.undo().unwrap().is_none());
+
+    vault.redo().unwrap().unwrap();
+    assert_eq!(vault.notes(image.id).unwrap(), "first");
+}
+
+#[test]
+fn annotations_round_trip_through_undo_and_redo() {
+    let vault = create_vault("history-annotations");
+    let image = import_png(&vault, "photo.png", [10, 20, 30]);
+    let input = |text: &str| AnnotationInput {
+        x2: 2.0,
+        y2: 2.0,
+        text: text.to_string(),
+        ..AnnotationInput::default()
+    };
+
+    let annotation = vault.add_annotation(image.id, &input("first")).unwrap();
+    vault
+        .update_annotation(annotation.id, &input("second"))
+        .unwrap();
+    vault.delete_annotation(annotation.id).unwrap();
+    assert!(annotation_texts(&vault, image.id).is_empty());
+
+    vault.undo().unwrap().unwrap();
+    assert_eq!(
+        annotation_texts(&vault, image.id),
+        vec!["second".to_string()]
+    );
+    vault.undo().unwrap().unwrap();
+    assert_eq!(
+        annotation_texts(&vault, image.id),
+        vec!["first".to_string()]
+    );
+    vault.undo().unwrap().unwrap();
+    assert!(annotation_texts(&vault, image.id).is_empty());
+
+    vault.redo().unwrap().unwrap();
+    let restored = vault.annotations(image.id).unwrap();
+    assert_eq!(restored.len(), 1);
+    assert_eq!(restored[0].id, annotation.id);
+    assert_eq!(restored[0].created_at, annotation.created_at);
+}
+
+#[test]
+fn tag_rules_round_trip_through_undo_and_redo() {
+    let vault = create_vault("history-tag-rules");
+    let input = |name: &str| TagRuleInput {
+        name: name.to_string(),
+        conditions: TagRuleConditions {
+            filename_pattern: Some("screenshot*".into()),
+            ..Default::default()
+        },
+        tags: vec!["screenshot".into()],
+        ..Default::default()
+    };
+
+    let rule = vault.create_tag_rule(&input("first")).unwrap();
+    vault.update_tag_rule(rule.id, &input("second")).unwrap();
+    vault.delete_tag_rule(rule.id).unwrap();
+    assert!(rule_names(&vault).is_empty());
+
+    vault.undo().unwrap().unwrap();
+    assert_eq!(rule_names(&vault), vec!["second".to_string()]);
+    vault.undo().unwrap().unwrap();
+    assert_eq!(rule_names(&vault), vec!["first".to_string()]);
+    vault.undo().unwrap().unwrap();
+    assert!(rule_names(&vault).is_empty());
+
+    vault.redo().unwrap().unwrap();
+    let restored = vault.tag_rules().unwrap();
+    assert_eq!(restored.len(), 1);
+    assert_eq!(restored[0].id, rule.id);
+    assert_eq!(restored[0].tags, vec!["screenshot".to_string()]);
+    assert_eq!(restored[0].created_at, rule.created_at);
+}
+
+#[test]
+fn smart_albums_round_trip_through_undo_and_redo() {
+    let vault = create_vault("history-smart-albums");
+    let input = |name: &str, query: &str| SmartAlbumInput {
+        name: name.to_string(),
+        query: query.to_string(),
+        pinned: false,
+    };
+
+    let album = vault.create_smart_album(&input("Cats", "tag:cat")).unwrap();
+    vault
+        .update_smart_album(album.id, &input("Dogs", "tag:dog"))
+        .unwrap();
+    vault.pin_smart_album(album.id, true).unwrap();
+    vault.delete_smart_album(album.id).unwrap();
+    assert!(smart_albums(&vault).is_empty());
+
+    vault.undo().unwrap().unwrap();
+    assert_eq!(
+        smart_albums(&vault),
+        vec![("Dogs".to_string(), "tag:dog".to_string(), true)]
+    );
+    vault.undo().unwrap().unwrap();
+    assert_eq!(
+        smart_albums(&vault),
+        vec![("Dogs".to_string(), "tag:dog".to_string(), false)]
+    );
+    vault.undo().unwrap().unwrap();
+    assert_eq!(
+        smart_albums(&vault),
+        vec![("Cats".to_string(), "tag:cat".to_string(), false)]
+    );
+    vault.undo().unwrap().unwrap();
+    assert!(smart_albums(&vault).is_empty());
+
+    vault.redo().unwrap().unwrap();
+    assert_eq!(vault.smart_albums().unwrap()[0].id, album.id);
+}
+
+#[cfg(unix)]
+#[test]
+fn ocr_retries_round_trip_through_undo_and_redo() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let tools = temp_dir("history-ocr-tools");
+    let tesseract = tools.join("tesseract");
+    std::fs::write(&tesseract, "#!/bin/sh\ncat \"$(dirname \"$0\")/text\"\n").unwrap();
+    std::fs::set_permissions(&tesseract, std::fs::Permissions::from_mode(0o755)).unwrap();
+    std::fs::write(tools.join("text"), "before").unwrap();
+
+    let vault = Vault::create(VaultConfig {
+        tesseract_path: Some(tesseract),
+        ..VaultConfig::new(temp_dir("history-ocr"))
+    })
+    .unwrap();
+    let image = import_png(&vault, "scan.png", [10, 20, 30]);
+    assert_eq!(vault.ocr_text(image.id).unwrap(), "before");
+
+    std::fs::write(tools.join("text"), "after").unwrap();
+    assert_eq!(vault.ocr_retry(image.id).unwrap(), "after");
+
+    vault.undo().unwrap().unwrap();
+    assert_eq!(vault.ocr_text(image.id).unwrap(), "before");
+
+    vault.redo().unwrap().unwrap();
+    assert_eq!(vault.ocr_text(image.id).unwrap(), "after");
+}
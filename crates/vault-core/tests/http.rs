@@ -0,0 +1,87 @@
+mod common;
+
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
+use common::{Server, response};
+use vault_core::{
+    models::network::{DomainRule, NetworkConfig},
+    utils::http_utils::HttpClient,
+};
+
+fn client_with_key(domain: &str) -> HttpClient {
+    HttpClient::new(&NetworkConfig {
+        domain_rules: vec![DomainRule {
+            domain: domain.to_string(),
+            headers: BTreeMap::from([("X-API-Key".to_string(), "secret".to_string())]),
+            cookies: BTreeMap::new(),
+        }],
+        ..Default::default()
+    })
+    .unwrap()
+}
+
+type Seen = Arc<Mutex<Vec<(String, Option<String>)>>>;
+
+fn recording_server(
+    redirect_to: impl Fn(&str) -> String + Send + Sync + 'static,
+) -> (Server, Seen) {
+    let seen = Seen::default();
+    let log = seen.clone();
+    let server = Server::start(move |request| {
+        log.lock().unwrap().push((
+            request.path.clone(),
+            request.header("x-api-key").map(str::to_string),
+        ));
+        if request.path == "/start" {
+            let location = redirect_to(request.header("host").unwrap_or_default());
+            response("302 Found", &[("Location", &location)], b"")
+        } else {
+            response("200 OK", &[("Content-Type", "text/plain")], b"done")
+        }
+    });
+    (server, seen)
+}
+
+#[tokio::test]
+async fn domain_headers_are_not_sent_to_other_hosts_after_a_redirect() {
+    let (server, seen) = recording_server(|host| {
+        format!("http://{}/landing", host.replace("127.0.0.1", "localhost"))
+    });
+    let client = client_with_key("127.0.0.1");
+
+    let resp = client.get(&server.url("/start")).await.unwrap();
+    assert_eq!(resp.url().host_str(), Some("localhost"));
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(
+        *seen,
+        vec![
+            ("/start".to_string(), Some("secret".to_string())),
+            ("/landing".to_string(), None),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn domain_headers_follow_redirects_on_the_same_host() {
+    let (server, seen) = recording_server(|_| "/landing".to_string());
+    let client = client_with_key("127.0.0.1");
+
+    client.get(&server.url("/start")).await.unwrap();
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 2);
+    assert!(seen.iter().all(|(_, key)| key.as_deref() == Some("secret")));
+}
+
+#[tokio::test]
+async fn redirect_loops_are_stopped() {
+    let (server, _) = recording_server(|_| "/start".to_string());
+    let client = client_with_key("example.com");
+
+    let error = client.get(&server.url("/start")).await.unwrap_err();
+    assert!(error.to_string().contains("redirected too many times"));
+}
@@ -0,0 +1,134 @@
+mod common;
+
+use exif::{Context, In, Reader, Tag};
+use image::{ImageFormat, RgbImage};
+use img_parts::{
+    Bytes, ImageEXIF,
+    jpeg::{Jpeg, JpegSegment, markers},
+};
+use vault_core::utils::metadata_utils;
+
+fn tiff(unknown_field: bool) -> Vec<u8> {
+    let entries: u16 = if unknown_field { 3 } else { 2 };
+    let gps_offset = 8 + 2 + 12 * entries as u32 + 4;
+
+    let mut bytes = b"II*\0".to_vec();
+    bytes.extend_from_slice(&8u32.to_le_bytes());
+    bytes.extend_from_slice(&entries.to_le_bytes());
+    let mut entry = |tag: u16, kind: u16, count: u32, value: [u8; 4]| {
+        bytes.extend_from_slice(&tag.to_le_bytes());
+        bytes.extend_from_slice(&kind.to_le_bytes());
+        bytes.extend_from_slice(&count.to_le_bytes());
+        bytes.extend_from_slice(&value);
+    };
+    entry(0x0112, 3, 1, [6, 0, 0, 0]);
+    entry(0x8825, 4, 1, gps_offset.to_le_bytes());
+    if unknown_field {
+        entry(0xc000, 99, 1, [0, 0, 0, 0]);
+    }
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&0x0001u16.to_le_bytes());
+    bytes.extend_from_slice(&2u16.to_le_bytes());
+    bytes.extend_from_slice(&2u32.to_le_bytes());
+    bytes.extend_from_slice(b"N\0\0\0");
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+    bytes
+}
+
+fn jpeg_with_exif(exif: Vec<u8>) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    RgbImage::new(4, 3)
+        .write_to(&mut std::io::Cursor::new(&mut bytes), ImageFormat::Jpeg)
+        .unwrap();
+
+    let mut jpeg = Jpeg::from_bytes(Bytes::from(bytes)).unwrap();
+    jpeg.set_exif(Some(Bytes::from(exif)));
+    jpeg.encoder().bytes().to_vec()
+}
+
+fn exif_of(bytes: &[u8]) -> exif::Exif {
+    let jpeg = Jpeg::from_bytes(Bytes::copy_from_slice(bytes)).unwrap();
+    Reader::new()
+        .read_raw(jpeg.exif().unwrap().to_vec())
+        .unwrap()
+}
+
+#[test]
+fn stripping_location_keeps_the_orientation() {
+    let original = jpeg_with_exif(tiff(false));
+    assert!(
+        exif_of(&original)
+            .fields()
+            .any(|field| field.tag.context() == Context::Gps)
+    );
+
+    let stripped = metadata_utils::strip_location(&original).unwrap().unwrap();
+    let exif = exif_of(&stripped);
+    assert!(
+        !exif
+            .fields()
+            .any(|field| field.tag.context() == Context::Gps)
+    );
+    assert_eq!(
+        exif.get_field(Tag::Orientation, In::PRIMARY)
+            .and_then(|field| field.value.get_uint(0)),
+        Some(6)
+    );
+}
+
+#[test]
+fn files_are_left_untouched_when_exif_cannot_be_rewritten() {
+    let original = jpeg_with_exif(tiff(true));
+    let path = common::temp_dir("metadata-untouched").join("photo.jpg");
+    std::fs::write(&path, &original).unwrap();
+
+    assert!(metadata_utils::strip_location_in_place(path.to_str().unwrap()).is_err());
+    assert_eq!(std::fs::read(&path).unwrap(), original);
+}
+
+fn xmp_segment(body: &str) -> JpegSegment {
+    let mut contents = b"http://ns.adobe.com/xap/1.0/\0".to_vec();
+    contents.extend_from_slice(body.as_bytes());
+    JpegSegment::new_with_contents(markers::APP1, Bytes::from(contents))
+}
+
+#[test]
+fn location_is_stripped_from_xmp_in_place() {
+    let mut jpeg = Jpeg::from_bytes(Bytes::from(jpeg_with_exif(tiff(false)))).unwrap();
+    jpeg.segments_mut().insert(
+        1,
+        xmp_segment(
+            "<rdf:Description exif:GPSLatitude=\"51,30.0N\" exif:GPSLongitude=\"0,7.5W\"/>",
+        ),
+    );
+    jpeg.segments_mut()
+        .insert(1, xmp_segment("<rdf:Description xmp:Rating=\"5\"/>"));
+
+    let dir = common::temp_dir("metadata-xmp");
+    let path = dir.join("photo.jpg");
+    std::fs::write(&path, jpeg.encoder().bytes()).unwrap();
+
+    assert!(metadata_utils::strip_location_in_place(path.to_str().unwrap()).unwrap());
+
+    let stripped = std::fs::read(&path).unwrap();
+    let xmp: Vec<String> = Jpeg::from_bytes(Bytes::from(stripped.clone()))
+        .unwrap()
+        .segments()
+        .iter()
+        .filter(|segment| segment.marker() == markers::APP1)
+        .map(|segment| String::from_utf8_lossy(segment.contents()).to_string())
+        .filter(|contents| contents.starts_with("http://ns.adobe.com/xap/1.0/"))
+        .collect();
+    assert_eq!(xmp.len(), 1);
+    assert!(!xmp[0].contains("GPS"));
+    assert!(
+        !exif_of(&stripped)
+            .fields()
+            .any(|field| field.tag.context() == Context::Gps)
+    );
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+    assert!(!metadata_utils::strip_location_in_place(path.to_str().unwrap()).unwrap());
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use common::temp_dir;
+use rusqlite::Connection;
+use vault_core::db::database;
+
+fn user_version(conn: &Connection) -> usize {
+    conn.query_row("PRAGMA user_version", [], |row| row.get(0))
+        .unwrap()
+}
+
+fn column_count(conn: &Connection, column: &str) -> i64 {
+    conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('images') WHERE name = ?1",
+        [column],
+        |row| row.get(0),
+    )
+    .unwrap()
+}
+
+#[test]
+fn failed_migrations_keep_earlier_ones_recorded() {
+    let conn = Connection::open(temp_dir("migrations-failed").join("imagevault.db")).unwrap();
+    conn.execute_batch(
+        "CREATE TABLE images (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            filename TEXT NOT NULL,
+            path TEXT NOT NULL,
+            added_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+            notes TEXT
+        )",
+    )
+    .unwrap();
+
+    assert!(database::run_migrations(&conn).is_err());
+    let failed = database::MIGRATIONS
+        .iter()
+        .position(|migration| migration.contains("ADD COLUMN notes"))
+        .unwrap();
+    assert_eq!(user_version(&conn), failed);
+    assert_eq!(column_count(&conn, "content_hash"), 1);
+
+    conn.execute_batch("ALTER TABLE images DROP COLUMN notes")
+        .unwrap();
+    database::run_migrations(&conn).unwrap();
+    assert_eq!(user_version(&conn), database::MIGRATIONS.len());
+    assert_eq!(column_count(&conn, "notes"), 1);
+}
+
+#[test]
+fn baseline_search_words_become_tags() {
+    let conn = Connection::open(temp_dir("migrations-baseline").join("imagevault.db")).unwrap();
+    conn.execute_batch(
+        "CREATE TABLE images (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            filename TEXT NOT NULL,
+            path TEXT NOT NULL,
+            added_at DATETIME DEFAULT CURRENT_TIMESTAMP
+        );
+        CREATE VIRTUAL TABLE image_search USING fts5(search_text);
+        CREATE TABLE sync_folders (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            path TEXT NOT NULL UNIQUE,
+            move_images BOOLEAN DEFAULT FALSE
+        );
+        INSERT INTO images (id, filename, path) VALUES (1, 'receipt.png', 'receipt.png');
+        INSERT INTO image_search (rowid, search_text) VALUES (1, 'TOTAL 12.50
+invoice finance');",
+    )
+    .unwrap();
+
+    database::run_migrations(&conn).unwrap();
+
+    assert_eq!(
+        database::get_tags(&conn, 1).unwrap(),
+        vec!["12.50", "finance", "invoice", "TOTAL"]
+    );
+    assert_eq!(database::get_ocr_text(&conn, 1).unwrap(), "");
+}
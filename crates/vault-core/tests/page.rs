@@ -0,0 +1,72 @@
+use serde_json::{Value, json};
+use vault_core::utils::page_utils;
+
+fn preview(html: &str, page_url: &str) -> Value {
+    serde_json::to_value(page_utils::extract_page_images(html, page_url).unwrap()).unwrap()
+}
+
+fn urls(preview: &Value) -> Vec<&str> {
+    preview["images"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|image| image["url"].as_str().unwrap())
+        .collect()
+}
+
+#[test]
+fn social_images_come_first_and_duplicates_are_dropped() {
+    let preview = preview(
+        include_str!("fixtures/article.html"),
+        "https://news.example.com/stories/harbour.html",
+    );
+
+    assert_eq!(preview["title"], "Harbour & Lights");
+    assert_eq!(
+        urls(&preview),
+        vec![
+            "https://cdn.example.com/social/cover.jpg",
+            "https://news.example.com/social/twitter.jpg",
+            "https://news.example.com/stories/photos/harbour.jpg",
+            "https://news.example.com/stories/photos/lazy.jpg",
+            "https://news.example.com/stories/photos/night-large.jpg",
+            "https://news.example.com/stories/photos/night-small.jpg",
+            "https://news.example.com/stories/icons/logo@2x.png",
+        ]
+    );
+
+    let images = preview["images"].as_array().unwrap();
+    assert_eq!(images[0]["kind"], "og:image");
+    assert_eq!(images[1]["kind"], "twitter:image");
+    assert_eq!(
+        images[2],
+        json!({
+            "url": "https://news.example.com/stories/photos/harbour.jpg",
+            "kind": "img",
+            "alt": "The harbour",
+            "width": 800,
+        })
+    );
+    assert_eq!(images[4]["kind"], "srcset");
+    assert_eq!(images[4]["width"], 1600);
+    assert_eq!(images[6]["width"], Value::Null);
+}
+
+#[test]
+fn relative_urls_resolve_against_the_base_element() {
+    let preview = preview(
+        include_str!("fixtures/base.html"),
+        "https://www.example.net/gallery/index.html",
+    );
+
+    assert_eq!(preview["title"], Value::Null);
+    assert_eq!(
+        urls(&preview),
+        vec![
+            "https://static.example.net/assets/gallery/one.png",
+            "https://static.example.net/shared/two.png",
+            "https://images.example.org/three.png",
+        ]
+    );
+    assert_eq!(preview["images"][0]["alt"], "One");
+}
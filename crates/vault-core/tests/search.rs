@@ -0,0 +1,49 @@
+mod common;
+
+use common::{create_vault, import_png};
+use vault_core::{Vault, models::image::ColorLabel};
+
+fn search(vault: &Vault, query: &str) -> Vec<String> {
+    let mut names: Vec<String> = vault
+        .search(query)
+        .unwrap()
+        .into_iter()
+        .map(|image| image.filename)
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn rating_and_label_filters_match_marked_images() {
+    let vault = create_vault("search-marks");
+    let unrated = import_png(&vault, "unrated.png", [10, 20, 30]);
+    let good = import_png(&vault, "good.png", [40, 50, 60]);
+    let best = import_png(&vault, "best.png", [70, 80, 90]);
+    vault.set_rating(good.id, 3).unwrap();
+    vault.set_rating(best.id, 5).unwrap();
+    vault
+        .set_color_label(best.id, Some(ColorLabel::Red))
+        .unwrap();
+    vault
+        .set_color_label(unrated.id, Some(ColorLabel::Blue))
+        .unwrap();
+
+    assert_eq!(search(&vault, "rating:>=3"), vec!["best.png", "good.png"]);
+    assert_eq!(search(&vault, "rating:<3"), vec!["unrated.png"]);
+    assert_eq!(search(&vault, "rating:=5"), vec!["best.png"]);
+    assert_eq!(search(&vault, "label:red"), vec!["best.png"]);
+    assert_eq!(
+        search(&vault, "label:blue label:none"),
+        vec!["good.png", "unrated.png"]
+    );
+    assert!(search(&vault, "rating:>5").is_empty());
+}
+
+#[test]
+fn ratings_below_zero_are_rejected() {
+    let vault = create_vault("search-below-zero");
+    import_png(&vault, "unrated.png", [10, 20, 30]);
+
+    assert!(vault.search("rating:<0").is_err());
+}
@@ -0,0 +1,168 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::{Server, create_vault, png, response};
+use reqwest::{Client, StatusCode, header};
+use serde_json::{Value, json};
+use vault_core::{
+    Vault,
+    utils::server_utils::{self, ApiServer},
+};
+
+const TOKEN: &str = "test-token";
+const BOUNDARY: &str = "vault-core-test-boundary";
+
+async fn start(name: &str) -> (Arc<Vault>, ApiServer, String) {
+    let vault = Arc::new(create_vault(name));
+    let server = server_utils::start(vault.clone(), 0, TOKEN).await.unwrap();
+    let base = format!("http://{}", server.addr());
+    (vault, server, base)
+}
+
+fn multipart(filename: &str, bytes: &[u8]) -> Vec<u8> {
+    let mut body = format!(
+        "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\nContent-Type: application/octet-stream\r\n\r\n",
+        BOUNDARY, filename
+    )
+    .into_bytes();
+    body.extend_from_slice(bytes);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", BOUNDARY).as_bytes());
+    body
+}
+
+async fn upload(client: &Client, base: &str, filename: &str, bytes: &[u8]) -> reqwest::Response {
+    client
+        .post(format!("{}/api/images", base))
+        .bearer_auth(TOKEN)
+        .header(
+            header::CONTENT_TYPE,
+            format!("multipart/form-data; boundary={}", BOUNDARY),
+        )
+        .body(multipart(filename, bytes))
+        .send()
+        .await
+        .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn requests_without_a_valid_token_are_rejected() {
+    let (_vault, server, base) = start("server-auth").await;
+    let client = Client::new();
+
+    let missing = client
+        .get(format!("{}/api/images", base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(missing.status(), StatusCode::UNAUTHORIZED);
+
+    let wrong = client
+        .get(format!("{}/api/images", base))
+        .bearer_auth("wrong-token")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(wrong.status(), StatusCode::UNAUTHORIZED);
+
+    let valid = client
+        .get(format!("{}/api/images", base))
+        .bearer_auth(TOKEN)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(valid.status(), StatusCode::OK);
+
+    let openapi = client
+        .get(format!("{}/openapi.json", base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(openapi.status(), StatusCode::OK);
+
+    server.stop().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn uploads_accept_images_and_reject_other_files() {
+    let (vault, server, base) = start("server-upload").await;
+    let client = Client::new();
+
+    let created = upload(&client, &base, "photo.png", &png(4, 3, [1, 2, 3])).await;
+    assert_eq!(created.status(), StatusCode::CREATED);
+    let images: Value = created.json().await.unwrap();
+    assert_eq!(images[0]["filename"], "photo.png");
+
+    let rejected = upload(&client, &base, "notes.png", b"just some text").await;
+    assert_eq!(rejected.status(), StatusCode::BAD_REQUEST);
+
+    assert_eq!(vault.images().unwrap().len(), 1);
+    assert_eq!(std::fs::read_dir(vault.image_dir()).unwrap().count(), 1);
+
+    server.stop().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn search_finds_tagged_uploads() {
+    let (_vault, server, base) = start("server-search").await;
+    let client = Client::new();
+
+    let images: Value = upload(&client, &base, "beach.png", &png(4, 3, [0, 0, 200]))
+        .await
+        .json()
+        .await
+        .unwrap();
+    let id = images[0]["id"].as_i64().unwrap();
+    upload(&client, &base, "forest.png", &png(4, 3, [0, 200, 0])).await;
+
+    let tags = client
+        .post(format!("{}/api/images/{}/tags", base, id))
+        .bearer_auth(TOKEN)
+        .json(&json!({ "tags": ["holiday"] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(tags.status(), StatusCode::OK);
+
+    let results: Value = client
+        .get(format!("{}/api/images/search", base))
+        .query(&[("q", "tag:holiday")])
+        .bearer_auth(TOKEN)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let ids: Vec<_> = results
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|image| image["id"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids, vec![id]);
+
+    server.stop().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn url_imports_store_the_downloaded_image() {
+    let (vault, server, base) = start("server-fetch").await;
+    let body = png(4, 3, [9, 9, 9]);
+    let remote =
+        Server::start(move |_| response("200 OK", &[("Content-Type", "image/png")], &body));
+
+    let created = Client::new()
+        .post(format!("{}/api/images/url", base))
+        .bearer_auth(TOKEN)
+        .json(&json!({ "url": remote.url("/pictures/remote.png") }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(created.status(), StatusCode::CREATED);
+    let image: Value = created.json().await.unwrap();
+    assert_eq!(image["filename"], "remote.png");
+    assert_eq!(vault.images().unwrap().len(), 1);
+
+    server.stop().await;
+}
@@ -0,0 +1,216 @@
+use crate::state::VaultState;
+use vault_core::models::{
+    album::{Album, AlbumFolder, AlbumInput, SmartAlbum, SmartAlbumInput},
+    image::Image,
+};
+
+#[tauri::command]
+pub fn list_albums(vault: tauri::State<VaultState>) -> Result<Vec<Album>, String> {
+    vault.get()?.albums().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_image_albums(
+    vault: tauri::State<VaultState>,
+    image_id: i64,
+) -> Result<Vec<Album>, String> {
+    vault
+        .get()?
+        .image_albums(image_id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn create_album(vault: tauri::State<VaultState>, album: AlbumInput) -> Result<Album, String> {
+    vault.get()?.create_album(&album).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn update_album(
+    vault: tauri::State<VaultState>,
+    album_id: i64,
+    album: AlbumInput,
+) -> Result<Album, String> {
+    vault
+        .get()?
+        .update_album(album_id, &album)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn rename_album(
+    vault: tauri::State<VaultState>,
+    album_id: i64,
+    name: &str,
+) -> Result<Album, String> {
+    vault
+        .get()?
+        .rename_album(album_id, name)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn delete_album(vault: tauri::State<VaultState>, album_id: i64) -> Result<(), String> {
+    vault
+        .get()?
+        .delete_album(album_id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_album_images(
+    vault: tauri::State<VaultState>,
+    album_id: i64,
+) -> Result<Vec<Image>, String> {
+    vault
+        .get()?
+        .album_images(album_id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn add_images_to_album(
+    vault: tauri::State<VaultState>,
+    album_id: i64,
+    image_ids: Vec<i64>,
+) -> Result<Album, String> {
+    vault
+        .get()?
+        .add_to_album(album_id, &image_ids)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn remove_images_from_album(
+    vault: tauri::State<VaultState>,
+    album_id: i64,
+    image_ids: Vec<i64>,
+) -> Result<Album, String> {
+    vault
+        .get()?
+        .remove_from_album(album_id, &image_ids)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn reorder_album(
+    vault: tauri::State<VaultState>,
+    album_id: i64,
+    image_ids: Vec<i64>,
+) -> Result<Vec<Image>, String> {
+    vault
+        .get()?
+        .reorder_album(album_id, &image_ids)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn list_album_folders(vault: tauri::State<VaultState>) -> Result<Vec<AlbumFolder>, String> {
+    vault.get()?.album_folders().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn create_album_folder(
+    vault: tauri::State<VaultState>,
+    name: &str,
+    parent_id: Option<i64>,
+) -> Result<i64, String> {
+    vault
+        .get()?
+        .create_album_folder(name, parent_id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn rename_album_folder(
+    vault: tauri::State<VaultState>,
+    folder_id: i64,
+    name: &str,
+) -> Result<(), String> {
+    vault
+        .get()?
+        .rename_album_folder(folder_id, name)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn move_album_folder(
+    vault: tauri::State<VaultState>,
+    folder_id: i64,
+    parent_id: Option<i64>,
+) -> Result<(), String> {
+    vault
+        .get()?
+        .move_album_folder(folder_id, parent_id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn delete_album_folder(vault: tauri::State<VaultState>, folder_id: i64) -> Result<(), String> {
+    vault
+        .get()?
+        .delete_album_folder(folder_id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn list_smart_albums(vault: tauri::State<VaultState>) -> Result<Vec<SmartAlbum>, String> {
+    vault.get()?.smart_albums().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn create_smart_album(
+    vault: tauri::State<VaultState>,
+    album: SmartAlbumInput,
+) -> Result<SmartAlbum, String> {
+    vault
+        .get()?
+        .create_smart_album(&album)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn update_smart_album(
+    vault: tauri::State<VaultState>,
+    smart_album_id: i64,
+    album: SmartAlbumInput,
+) -> Result<SmartAlbum, String> {
+    vault
+        .get()?
+        .update_smart_album(smart_album_id, &album)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn pin_smart_album(
+    vault: tauri::State<VaultState>,
+    smart_album_id: i64,
+    pinned: bool,
+) -> Result<SmartAlbum, String> {
+    vault
+        .get()?
+        .pin_smart_album(smart_album_id, pinned)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn delete_smart_album(
+    vault: tauri::State<VaultState>,
+    smart_album_id: i64,
+) -> Result<(), String> {
+    vault
+        .get()?
+        .delete_smart_album(smart_album_id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_smart_album_images(
+    vault: tauri::State<VaultState>,
+    smart_album_id: i64,
+) -> Result<Vec<Image>, String> {
+    vault
+        .get()?
+        .smart_album_images(smart_album_id)
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,65 @@
+use crate::state::VaultState;
+use vault_core::models::annotation::{Annotation, AnnotationInput};
+
+#[tauri::command]
+pub fn get_image_notes(vault: tauri::State<VaultState>, image_id: i64) -> Result<String, String> {
+    vault.get()?.notes(image_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn set_image_notes(
+    vault: tauri::State<VaultState>,
+    image_id: i64,
+    notes: &str,
+) -> Result<(), String> {
+    vault
+        .get()?
+        .set_notes(image_id, notes)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn list_annotations(
+    vault: tauri::State<VaultState>,
+    image_id: i64,
+) -> Result<Vec<Annotation>, String> {
+    vault
+        .get()?
+        .annotations(image_id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn add_annotation(
+    vault: tauri::State<VaultState>,
+    image_id: i64,
+    annotation: AnnotationInput,
+) -> Result<Annotation, String> {
+    vault
+        .get()?
+        .add_annotation(image_id, &annotation)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn update_annotation(
+    vault: tauri::State<VaultState>,
+    annotation_id: i64,
+    annotation: AnnotationInput,
+) -> Result<Annotation, String> {
+    vault
+        .get()?
+        .update_annotation(annotation_id, &annotation)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn delete_annotation(
+    vault: tauri::State<VaultState>,
+    annotation_id: i64,
+) -> Result<(), String> {
+    vault
+        .get()?
+        .delete_annotation(annotation_id)
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,64 @@
+use crate::state::{ApiServerState, AppVault};
+use tauri::{AppHandle, Manager};
+use vault_core::{
+    models::app_config::ApiConfig,
+    utils::{config_utils, server_utils},
+};
+
+pub async fn restart_api_server(app: &AppHandle) -> Result<ApiConfig, String> {
+    let mut config = config_utils::load_config().map_err(|e| e.to_string())?;
+
+    let state = app.state::<ApiServerState>();
+    if let Some(server) = state.take()? {
+        server.stop().await;
+    }
+
+    if !config.api.enabled {
+        return Ok(config.api);
+    }
+
+    let token = match &config.api.token {
+        Some(token) => token.clone(),
+        None => {
+            let token = server_utils::generate_token();
+            config.api.token = Some(token.clone());
+            config_utils::save_config(&config).map_err(|e| e.to_string())?;
+            token
+        }
+    };
+
+    let server = server_utils::start(AppVault(app.clone()), config.api.port, &token)
+        .await
+        .map_err(|e| e.to_string())?;
+    state.set(server)?;
+
+    Ok(config.api)
+}
+
+#[tauri::command]
+pub fn get_api_settings() -> Result<ApiConfig, String> {
+    Ok(config_utils::load_config().map_err(|e| e.to_string())?.api)
+}
+
+#[tauri::command]
+pub async fn update_api_settings(
+    app: AppHandle,
+    enabled: bool,
+    port: u16,
+) -> Result<ApiConfig, String> {
+    let mut config = config_utils::load_config().map_err(|e| e.to_string())?;
+    config.api.enabled = enabled;
+    config.api.port = port;
+    config_utils::save_config(&config).map_err(|e| e.to_string())?;
+
+    restart_api_server(&app).await
+}
+
+#[tauri::command]
+pub async fn regenerate_api_token(app: AppHandle) -> Result<ApiConfig, String> {
+    let mut config = config_utils::load_config().map_err(|e| e.to_string())?;
+    config.api.token = Some(server_utils::generate_token());
+    config_utils::save_config(&config).map_err(|e| e.to_string())?;
+
+    restart_api_server(&app).await
+}
@@ -0,0 +1,79 @@
+use std::{path::PathBuf, thread, time::Duration};
+
+use crate::state::VaultState;
+use chrono::{DateTime, Utc};
+use tauri::{AppHandle, Manager};
+use vault_core::{
+    models::backup::{BackupSummary, RestoreSummary},
+    utils::backup_utils,
+};
+
+const BACKUP_CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+#[tauri::command]
+pub fn create_backup(
+    vault: tauri::State<VaultState>,
+    destination: Option<String>,
+    incremental: bool,
+) -> Result<BackupSummary, String> {
+    let vault = vault.get()?;
+    let destination = match destination {
+        Some(destination) => destination,
+        None => vault
+            .settings()
+            .map_err(|e| e.to_string())?
+            .backup_directory
+            .ok_or("No backup directory configured")?,
+    };
+
+    backup_utils::backup_vault(&vault, &PathBuf::from(destination), incremental)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn restore_backup(
+    vault: tauri::State<VaultState>,
+    path: &str,
+) -> Result<RestoreSummary, String> {
+    let vault = vault.get()?;
+    backup_utils::restore_vault(&vault, &PathBuf::from(path)).map_err(|e| e.to_string())
+}
+
+fn run_scheduled_backup(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+    let Ok(vault) = app.state::<VaultState>().get() else {
+        return Ok(());
+    };
+    let settings = vault.settings()?;
+
+    let Some(directory) = settings.backup_directory else {
+        return Ok(());
+    };
+    if settings.backup_interval_hours == 0 {
+        return Ok(());
+    }
+
+    let directory = PathBuf::from(directory);
+    if let Some(latest) = backup_utils::latest_backup(&directory)? {
+        let manifest = backup_utils::read_manifest(&latest)?;
+        let created_at = DateTime::parse_from_rfc3339(&manifest.created_at)?;
+        let interval = chrono::Duration::hours(settings.backup_interval_hours.into());
+        if Utc::now().signed_duration_since(created_at) < interval {
+            return Ok(());
+        }
+    }
+
+    backup_utils::backup_vault(&vault, &directory, settings.backup_incremental)?;
+
+    Ok(())
+}
+
+pub fn spawn_backup_scheduler(app: AppHandle) {
+    thread::spawn(move || {
+        loop {
+            if let Err(e) = run_scheduled_backup(&app) {
+                eprintln!("Warning: Scheduled backup failed: {}", e);
+            }
+            thread::sleep(BACKUP_CHECK_INTERVAL);
+        }
+    });
+}
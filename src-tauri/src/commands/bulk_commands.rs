@@ -0,0 +1,54 @@
+use crate::state::VaultState;
+use vault_core::{
+    models::bulk::{BulkItemResult, BulkSelection},
+    utils::bulk_utils,
+};
+
+#[tauri::command]
+pub fn add_tags_bulk(
+    vault: tauri::State<VaultState>,
+    selection: BulkSelection,
+    tags: Vec<String>,
+) -> Result<Vec<BulkItemResult>, String> {
+    let vault = vault.get()?;
+    bulk_utils::add_tags(&vault, selection, &tags).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn remove_tags_bulk(
+    vault: tauri::State<VaultState>,
+    selection: BulkSelection,
+    tags: Vec<String>,
+) -> Result<Vec<BulkItemResult>, String> {
+    let vault = vault.get()?;
+    bulk_utils::remove_tags(&vault, selection, &tags).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn delete_images_bulk(
+    vault: tauri::State<VaultState>,
+    selection: BulkSelection,
+) -> Result<Vec<BulkItemResult>, String> {
+    let vault = vault.get()?;
+    bulk_utils::delete_images(&vault, selection).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn ocr_retry_bulk(
+    vault: tauri::State<VaultState>,
+    selection: BulkSelection,
+) -> Result<Vec<BulkItemResult>, String> {
+    let vault = vault.get()?;
+    bulk_utils::ocr_retry(&vault, selection).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn move_to_album_bulk(
+    vault: tauri::State<VaultState>,
+    selection: BulkSelection,
+    album_id: i64,
+    from_album_id: Option<i64>,
+) -> Result<Vec<BulkItemResult>, String> {
+    let vault = vault.get()?;
+    bulk_utils::move_to_album(&vault, selection, album_id, from_album_id).map_err(|e| e.to_string())
+}
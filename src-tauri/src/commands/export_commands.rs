@@ -0,0 +1,44 @@
+use std::path::Path;
+
+use crate::state::VaultState;
+use tauri::{Emitter, State, Window};
+use vault_core::{
+    models::export::{ExportOptions, ExportSelection, ExportSummary},
+    utils::export_utils,
+};
+
+#[tauri::command]
+pub fn export_image(
+    vault: State<VaultState>,
+    image_id: i64,
+    destination: &str,
+    strip_metadata: bool,
+) -> Result<String, String> {
+    let vault = vault.get()?;
+    let target =
+        export_utils::export_image(&vault, image_id, Path::new(destination), strip_metadata)
+            .map_err(|e| e.to_string())?;
+
+    Ok(target.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+pub fn export_images(
+    window: Window,
+    vault: State<VaultState>,
+    selection: ExportSelection,
+    destination: &str,
+    options: ExportOptions,
+) -> Result<ExportSummary, String> {
+    let vault = vault.get()?;
+    export_utils::export_images(
+        &vault,
+        selection,
+        Path::new(destination),
+        &options,
+        |count, total| {
+            let _ = window.emit("export_progress", (count, total));
+        },
+    )
+    .map_err(|e| e.to_string())
+}
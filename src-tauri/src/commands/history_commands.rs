@@ -0,0 +1,25 @@
+use crate::state::VaultState;
+use vault_core::models::history::HistoryEntry;
+
+const HISTORY_LIMIT: usize = 100;
+
+#[tauri::command]
+pub fn get_history(
+    vault: tauri::State<VaultState>,
+    limit: Option<usize>,
+) -> Result<Vec<HistoryEntry>, String> {
+    vault
+        .get()?
+        .history(limit.unwrap_or(HISTORY_LIMIT))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn undo(vault: tauri::State<VaultState>) -> Result<Option<HistoryEntry>, String> {
+    vault.get()?.undo().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn redo(vault: tauri::State<VaultState>) -> Result<Option<HistoryEntry>, String> {
+    vault.get()?.redo().map_err(|e| e.to_string())
+}
@@ -1,415 +1,261 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::{
-    db::database::{self, Db},
-    models::image::Image,
-    utils::{image_utils, path_utils},
-};
-use arboard::Clipboard;
-use rusqlite::{OptionalExtension, params};
-use std::fs;
+use crate::state::VaultState;
 use tauri::{Emitter, State, Window};
+use vault_core::{
+    models::{
+        clipboard::CopyMode,
+        image::{ColorLabel, Image, ImageDetails},
+        metadata::MetadataReport,
+        page::PagePreview,
+        search::ImageSort,
+        source::{ImageSource, SourceType},
+        url_import::UrlImportReport,
+    },
+    utils::{clipboard_utils, fetch_utils, image_utils, page_utils},
+};
 
-fn sanitize_fts_token(token: &str) -> String {
-    token
-        .chars()
-        .filter(|c| c.is_alphanumeric() || *c == '_' || *c == '-')
-        .collect()
+#[tauri::command]
+pub fn get_images(vault: State<VaultState>, sort: Option<ImageSort>) -> Result<Vec<Image>, String> {
+    vault
+        .get()?
+        .sorted_images(&sort.unwrap_or_default())
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub fn get_images(db: tauri::State<Db>) -> Result<Vec<Image>, String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
-
-    let mut stmt = conn
-        .prepare("SELECT id, filename, path, added_at FROM images")
-        .map_err(|e| e.to_string())?;
-
-    let image_iter = stmt
-        .query_map(params![], |row| {
-            Ok(Image {
-                id: row.get(0)?,
-                filename: row.get(1)?,
-                path: row.get(2)?,
-                added_at: row.get(3)?,
-            })
-        })
-        .map_err(|e| e.to_string())?;
-
-    let mut images = Vec::new();
-    for image in image_iter {
-        images.push(image.map_err(|e| e.to_string())?);
-    }
-
-    Ok(images)
+pub fn add_tag(vault: State<VaultState>, image_id: i64, new_tag: &str) -> Result<(), String> {
+    vault
+        .get()?
+        .add_tag(image_id, new_tag)
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub fn add_tag(db: tauri::State<Db>, image_id: i64, new_tag: &str) -> Result<(), String> {
-    let mut conn = db.0.lock().map_err(|e| e.to_string())?;
-
-    let trimmed = new_tag.trim();
-    if trimmed.is_empty() {
-        return Ok(());
-    }
-
-    let existing: Option<String> = conn
-        .query_row(
-            "SELECT search_text FROM image_search WHERE rowid = ?1",
-            [image_id],
-            |row| row.get(0),
-        )
-        .optional()
-        .map_err(|e| e.to_string())?;
-
-    let new_text = match existing {
-        Some(current) => {
-            // If tag already present, no-op
-            let has_tag = current.split_whitespace().any(|t| t == trimmed);
-            if has_tag {
-                return Ok(());
-            }
-            if current.is_empty() {
-                trimmed.to_string()
-            } else {
-                format!("{} {}", current, trimmed)
-            }
-        }
-        None => trimmed.to_string(),
-    };
-
-    let tx = conn.transaction().map_err(|e| e.to_string())?;
-    tx.execute("DELETE FROM image_search WHERE rowid = ?1", [image_id])
-        .map_err(|e| e.to_string())?;
-    tx.execute(
-        "INSERT INTO image_search (rowid, search_text) VALUES (?1, ?2)",
-        (image_id, new_text),
-    )
-    .map_err(|e| e.to_string())?;
-    tx.commit().map_err(|e| e.to_string())?;
-
-    Ok(())
+pub fn get_image_details(vault: State<VaultState>, image_id: i64) -> Result<ImageDetails, String> {
+    vault
+        .get()?
+        .image_details(image_id)
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub fn get_tags(db: tauri::State<Db>, image_id: i64) -> Result<Vec<String>, String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
-
-    let mut stmt = conn
-        .prepare("SELECT search_text FROM image_search WHERE rowid = ?1")
-        .map_err(|e| e.to_string())?;
-
-    let tag_iter = stmt
-        .query_map([image_id], |row| {
-            let search_text: String = row.get(0)?;
-            Ok(search_text)
-        })
-        .map_err(|e| e.to_string())?;
-
-    let mut all_tags = Vec::new();
-    for tag_result in tag_iter {
-        let tag_text = tag_result.map_err(|e| e.to_string())?;
-        for tag in tag_text.split_whitespace() {
-            if !tag.is_empty() {
-                all_tags.push(tag.to_string());
-            }
-        }
-    }
-
-    all_tags.sort();
-    all_tags.dedup();
-
-    Ok(all_tags)
+pub fn get_tags(vault: State<VaultState>, image_id: i64) -> Result<Vec<String>, String> {
+    vault.get()?.tags(image_id).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub fn search_images(db: tauri::State<Db>, tag: &str) -> Result<Vec<Image>, String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
-
-    let mut stmt = conn
-        .prepare(
-            "SELECT images.id, images.filename, images.path, images.added_at
-         FROM image_search
-         JOIN images ON images.id = image_search.rowid
-         WHERE image_search MATCH ?1",
-        )
-        .map_err(|e| e.to_string())?;
-
-    let query = tag
-        .split_whitespace()
-        .filter(|t| !t.is_empty())
-        .map(|t| {
-            let t = sanitize_fts_token(t);
-            if t.is_empty() { t } else { format!("{}*", t) }
-        })
-        .filter(|t| !t.is_empty())
-        .collect::<Vec<_>>()
-        .join(" ");
-
-    let image_iter = stmt
-        .query_map([query], |row| {
-            Ok(Image {
-                id: row.get(0)?,
-                filename: row.get(1)?,
-                path: row.get(2)?,
-                added_at: row.get(3)?,
-            })
-        })
-        .map_err(|e| e.to_string())?;
-
-    let mut images = Vec::new();
-    for image in image_iter {
-        images.push(image.map_err(|e| e.to_string())?);
-    }
+pub fn search_images(vault: State<VaultState>, tag: &str) -> Result<Vec<Image>, String> {
+    vault.get()?.search(tag).map_err(|e| e.to_string())
+}
 
-    Ok(images)
+#[tauri::command]
+pub fn set_image_rating(
+    vault: State<VaultState>,
+    image_id: i64,
+    rating: u8,
+) -> Result<Image, String> {
+    vault
+        .get()?
+        .set_rating(image_id, rating)
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub fn ocr_retry(db: tauri::State<Db>, image_id: i64) -> Result<(), String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
+pub fn set_image_favorite(
+    vault: State<VaultState>,
+    image_id: i64,
+    favorite: bool,
+) -> Result<Image, String> {
+    vault
+        .get()?
+        .set_favorite(image_id, favorite)
+        .map_err(|e| e.to_string())
+}
 
-    let mut stmt = conn
-        .prepare("SELECT path FROM images WHERE id = ?1")
-        .map_err(|e| e.to_string())?;
+#[tauri::command]
+pub fn set_image_color_label(
+    vault: State<VaultState>,
+    image_id: i64,
+    color_label: Option<ColorLabel>,
+) -> Result<Image, String> {
+    vault
+        .get()?
+        .set_color_label(image_id, color_label)
+        .map_err(|e| e.to_string())
+}
 
-    let path: String = stmt
-        .query_row([image_id], |row| row.get(0))
+#[tauri::command]
+pub fn ocr_retry(vault: State<VaultState>, image_id: i64) -> Result<(), String> {
+    vault
+        .get()?
+        .ocr_retry(image_id)
         .map_err(|e| e.to_string())?;
-
-    let text = image_utils::extract_text_from_image(&path).map_err(|e| e.to_string())?;
-
-    drop(stmt);
-    conn.execute(
-        "UPDATE image_search SET search_text = ?1 WHERE rowid = ?2",
-        (text, image_id),
-    )
-    .map_err(|e| e.to_string())?;
-
     Ok(())
 }
 
 #[tauri::command]
-pub fn remove_tag(db: tauri::State<Db>, image_id: i64, tag: &str) -> Result<(), String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
-
-    let mut stmt = conn
-        .prepare("DELETE FROM image_search WHERE rowid = ?1 AND search_text = ?2")
-        .map_err(|e| e.to_string())?;
-
-    let trimmed = tag.trim();
-    if trimmed.is_empty() {
-        return Ok(());
-    }
-
-    stmt.execute((image_id, trimmed))
-        .map_err(|e| e.to_string())?;
-
-    Ok(())
+pub fn remove_tag(vault: State<VaultState>, image_id: i64, tag: &str) -> Result<(), String> {
+    vault
+        .get()?
+        .remove_tag(image_id, tag)
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub fn save_image_from_path(
-    db: tauri::State<Db>,
+    vault: State<VaultState>,
     path: &str,
     move_image: bool,
 ) -> Result<(), String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
-    let new_path = image_utils::save_local_image(PathBuf::from(path), move_image)
+    vault
+        .get()?
+        .import_file(Path::new(path), move_image)
         .map_err(|e| e.to_string())?;
-
-    database::add_image(
-        &conn,
-        new_path
-            .file_name()
-            .ok_or("Invalid filename")?
-            .to_str()
-            .ok_or("Invalid filename")?,
-        new_path.to_str().ok_or("Invalid path")?,
-    )
-    .map_err(|e| e.to_string())?;
-
     Ok(())
 }
 
 #[tauri::command]
-// TODO: Make it faster by processing images in parallel
 pub fn save_image_from_folder(
     window: Window,
-    db: State<Db>,
+    vault: State<VaultState>,
     path: &str,
     move_image: bool,
 ) -> Result<(), String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
-
-    let folder_path = PathBuf::from(path);
-    let entries: Vec<_> = fs::read_dir(folder_path)
-        .map_err(|e| e.to_string())?
-        .collect::<Result<_, _>>()
+    vault
+        .get()?
+        .import_folder(Path::new(path), move_image, |count, total| {
+            let _ = window.emit("save_images_progress", (count, total));
+        })
         .map_err(|e| e.to_string())?;
-
-    let total = entries.len();
-    let mut count = 0;
-
-    for entry in entries {
-        let entry_path = entry.path();
-        if entry_path.is_dir() {
-            continue;
-        }
-
-        if !entry_path.extension().map_or(false, |ext| {
-            ext == "png" || ext == "jpg" || ext == "jpeg" || ext == "webp" || ext == "gif"
-        }) {
-            continue;
-        }
-
-        let filename = entry
-            .file_name()
-            .to_str()
-            .ok_or("Invalid filename")?
-            .to_string();
-
-        let new_path =
-            image_utils::save_local_image(entry_path, move_image).map_err(|e| e.to_string())?;
-
-        database::add_image(&conn, &filename, new_path.to_str().ok_or("Invalid path")?)
-            .map_err(|e| e.to_string())?;
-
-        count += 1;
-
-        window
-            .emit("save_images_progress", (count, total))
-            .map_err(|e| e.to_string())?;
-    }
-
     Ok(())
 }
 
 #[tauri::command]
-pub fn delete_image(db: tauri::State<Db>, image_id: i64) -> Result<(), String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
-
-    let mut stmt = conn
-        .prepare("SELECT path FROM images WHERE id = ?1")
-        .map_err(|e| e.to_string())?;
-    let path: String = stmt
-        .query_row([image_id], |row| row.get(0))
-        .map_err(|e| e.to_string())?;
-
-    conn.execute("DELETE FROM images WHERE id = ?1", [image_id])
-        .map_err(|e| e.to_string())?;
-    conn.execute("DELETE FROM image_search WHERE rowid = ?1", [image_id])
-        .map_err(|e| e.to_string())?;
-
-    std::fs::remove_file(path).map_err(|e| e.to_string())?;
-
-    drop(stmt);
-
-    Ok(())
+pub fn delete_image(vault: State<VaultState>, image_id: i64) -> Result<(), String> {
+    vault
+        .get()?
+        .delete_image(image_id)
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub fn copy_image_to_clipboard(path: String) -> Result<(), String> {
-    let mut clipboard = Clipboard::new().map_err(|e| e.to_string())?;
-
-    let bytes = fs::read(&path).map_err(|e| e.to_string())?;
+    clipboard_utils::copy_image(&PathBuf::from(path)).map_err(|e| e.to_string())
+}
 
-    let img = image::load_from_memory(&bytes).map_err(|e| e.to_string())?;
-    let rgba = img.to_rgba8();
+#[tauri::command]
+pub fn copy_images_to_clipboard(
+    vault: State<VaultState>,
+    image_ids: Vec<i64>,
+    mode: CopyMode,
+) -> Result<(), String> {
+    let vault = vault.get()?;
+    clipboard_utils::copy_images(&vault, &image_ids, mode).map_err(|e| e.to_string())
+}
 
-    let (width, height) = rgba.dimensions();
+#[tauri::command]
+pub fn get_metadata_report(
+    vault: State<VaultState>,
+    image_id: i64,
+) -> Result<MetadataReport, String> {
+    vault
+        .get()?
+        .metadata_report(image_id)
+        .map_err(|e| e.to_string())
+}
 
-    clipboard
-        .set_image(arboard::ImageData {
-            width: width as usize,
-            height: height as usize,
-            bytes: std::borrow::Cow::Owned(rgba.into_raw()),
-        })
+#[tauri::command]
+pub fn save_image_blob(vault: State<VaultState>, blob: String) -> Result<String, String> {
+    let (bytes, extension) = image_utils::decode_data_url(&blob).map_err(|e| e.to_string())?;
+
+    let filename = image_utils::pasted_filename(extension);
+    let source = ImageSource::new(SourceType::Clipboard, None);
+    let image = vault
+        .get()?
+        .import_bytes(&filename, &bytes, &source)
         .map_err(|e| e.to_string())?;
 
-    Ok(())
+    Ok(image.path)
 }
 
 #[tauri::command]
-pub fn save_image_blob(db: tauri::State<Db>, blob: String) -> Result<String, String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
-
-    let data = blob.split(',').last().unwrap_or("");
-    //TODO: change from deprected function
-    let bytes = base64::decode(data).map_err(|e| e.to_string())?;
-
-    let mut path = path_utils::get_image_path();
-
-    fs::create_dir_all(&path).map_err(|e| e.to_string())?;
-
-    let filename = format!("pasted_{}.png", chrono::Utc::now().timestamp());
-    path.push(filename.clone());
-
-    fs::write(&path, &bytes).map_err(|e| e.to_string())?;
-
-    database::add_image(&conn, &filename, path.to_str().ok_or("Invalid path")?)
-        .map_err(|e| e.to_string())?;
-
-    Ok(path.to_string_lossy().to_string())
+pub async fn paste_from_clipboard(vault: State<'_, VaultState>) -> Result<Vec<Image>, String> {
+    clipboard_utils::paste(vault.get()?)
+        .await
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub async fn fetch_and_save_image(db: tauri::State<'_, Db>, url: String) -> Result<String, String> {
-    let path = image_utils::save_image_from_url(url)
+pub async fn fetch_and_save_image(
+    vault: State<'_, VaultState>,
+    url: String,
+) -> Result<String, String> {
+    let image = vault
+        .get()?
+        .fetch_url(&url)
         .await
         .map_err(|e| e.to_string())?;
 
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
-    database::add_image(
-        &conn,
-        &path
-            .file_name()
-            .unwrap_or(std::ffi::OsStr::new("image"))
-            .to_str()
-            .unwrap_or("image"),
-        path.to_str().ok_or("Invalid path")?,
-    )
-    .map_err(|e| e.to_string())?;
-
-    Ok(path.to_string_lossy().to_string())
+    Ok(image.path)
 }
 
 #[tauri::command]
 pub async fn fetch_and_save_from_file(
-    db: tauri::State<'_, Db>,
+    window: Window,
+    vault: State<'_, VaultState>,
     path: String,
-) -> Result<(), String> {
+) -> Result<UrlImportReport, String> {
+    let vault = vault.get()?;
+
     let file_path = PathBuf::from(path);
-    let file_content = fs::read_to_string(file_path).map_err(|e| e.to_string())?;
+    let file_content = std::fs::read_to_string(file_path).map_err(|e| e.to_string())?;
 
-    let lines = file_content.lines().collect::<Vec<_>>();
+    Ok(
+        fetch_utils::import_urls(vault, &file_content, |count, total| {
+            let _ = window.emit("fetch_urls_progress", (count, total));
+        })
+        .await,
+    )
+}
 
-    for line in lines {
-        let url = line.trim();
-        let path = image_utils::save_image_from_url(url.to_string())
-            .await
-            .map_err(|e| e.to_string())?;
+#[tauri::command]
+pub async fn preview_page_images(
+    vault: State<'_, VaultState>,
+    url: String,
+) -> Result<PagePreview, String> {
+    let http = vault.get()?.http();
 
-        let conn = db.0.lock().map_err(|e| e.to_string())?;
-        database::add_image(
-            &conn,
-            &path
-                .file_name()
-                .unwrap_or(std::ffi::OsStr::new("image"))
-                .to_str()
-                .unwrap_or("image"),
-            path.to_str().ok_or("Invalid path")?,
-        )
-        .map_err(|e| e.to_string())?;
-    }
+    page_utils::preview_page_images(&http, &url)
+        .await
+        .map_err(|e| e.to_string())
+}
 
-    Ok(())
+#[tauri::command]
+pub async fn import_page_images(
+    window: Window,
+    vault: State<'_, VaultState>,
+    page_url: String,
+    image_urls: Vec<String>,
+) -> Result<UrlImportReport, String> {
+    let vault = vault.get()?;
+
+    Ok(
+        fetch_utils::import_page_images(vault, &page_url, image_urls, |count, total| {
+            let _ = window.emit("fetch_urls_progress", (count, total));
+        })
+        .await,
+    )
 }
 
 #[tauri::command]
-pub fn add_sync_folder(db: tauri::State<Db>, path: String, move_images: bool) -> Result<(), String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
-    database::add_sync_folder(&conn, path, move_images)
-        .map_err(|e| e.to_string())?;
-    Ok(())
-}
\ No newline at end of file
+pub fn add_sync_folder(
+    vault: State<VaultState>,
+    path: String,
+    move_images: bool,
+) -> Result<(), String> {
+    vault
+        .get()?
+        .add_sync_folder(path, move_images)
+        .map_err(|e| e.to_string())
+}
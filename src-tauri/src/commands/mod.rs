@@ -1 +1,11 @@
+pub mod album_commands;
+pub mod annotation_commands;
+pub mod api_commands;
+pub mod backup_commands;
+pub mod bulk_commands;
+pub mod export_commands;
+pub mod history_commands;
 pub mod image_commands;
+pub mod settings_commands;
+pub mod tag_commands;
+pub mod vault_commands;
@@ -0,0 +1,45 @@
+use crate::state::VaultState;
+use vault_core::{
+    models::{network::NetworkConfig, settings::Settings},
+    utils::{config_utils, http_utils::HttpClient},
+};
+
+#[tauri::command]
+pub fn get_settings(vault: tauri::State<VaultState>) -> Result<Settings, String> {
+    vault.get()?.settings().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn update_settings(vault: tauri::State<VaultState>, settings: Settings) -> Result<(), String> {
+    vault
+        .get()?
+        .update_settings(&settings)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_network_settings() -> Result<NetworkConfig, String> {
+    Ok(config_utils::load_config()
+        .map_err(|e| e.to_string())?
+        .network)
+}
+
+#[tauri::command]
+pub fn update_network_settings(
+    vault: tauri::State<VaultState>,
+    network: NetworkConfig,
+) -> Result<(), String> {
+    HttpClient::new(&network).map_err(|e| e.to_string())?;
+
+    let mut config = config_utils::load_config().map_err(|e| e.to_string())?;
+    config.network = network;
+    config_utils::save_config(&config).map_err(|e| e.to_string())?;
+
+    if let Ok(vault) = vault.get() {
+        vault
+            .set_network(&config.network)
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
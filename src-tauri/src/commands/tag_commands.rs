@@ -0,0 +1,156 @@
+use crate::state::VaultState;
+use vault_core::{
+    models::{
+        tag::{TagAlias, TagImplication, TagUsage},
+        tag_rule::{TagRule, TagRuleInput, TagRuleMatch},
+    },
+    utils::rule_utils,
+};
+
+const AUTOCOMPLETE_LIMIT: usize = 10;
+
+#[tauri::command]
+pub fn list_tags(vault: tauri::State<VaultState>) -> Result<Vec<TagUsage>, String> {
+    vault.get()?.list_tags().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn autocomplete_tags(
+    vault: tauri::State<VaultState>,
+    query: &str,
+    limit: Option<usize>,
+) -> Result<Vec<TagUsage>, String> {
+    vault
+        .get()?
+        .autocomplete_tags(query, limit.unwrap_or(AUTOCOMPLETE_LIMIT))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn rename_tag(vault: tauri::State<VaultState>, from: &str, to: &str) -> Result<(), String> {
+    vault.get()?.rename_tag(from, to).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn merge_tags(
+    vault: tauri::State<VaultState>,
+    tags: Vec<String>,
+    target: &str,
+) -> Result<(), String> {
+    vault
+        .get()?
+        .merge_tags(&tags, target)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_unused_tags(vault: tauri::State<VaultState>) -> Result<Vec<String>, String> {
+    vault.get()?.unused_tags().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn list_tag_aliases(vault: tauri::State<VaultState>) -> Result<Vec<TagAlias>, String> {
+    vault.get()?.tag_aliases().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn add_tag_alias(
+    vault: tauri::State<VaultState>,
+    alias: &str,
+    tag: &str,
+) -> Result<(), String> {
+    vault
+        .get()?
+        .add_tag_alias(alias, tag)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn remove_tag_alias(vault: tauri::State<VaultState>, alias: &str) -> Result<(), String> {
+    vault
+        .get()?
+        .remove_tag_alias(alias)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn list_tag_implications(
+    vault: tauri::State<VaultState>,
+) -> Result<Vec<TagImplication>, String> {
+    vault.get()?.tag_implications().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn add_tag_implication(
+    vault: tauri::State<VaultState>,
+    tag: &str,
+    implied_tag: &str,
+) -> Result<(), String> {
+    vault
+        .get()?
+        .add_tag_implication(tag, implied_tag)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn remove_tag_implication(
+    vault: tauri::State<VaultState>,
+    tag: &str,
+    implied_tag: &str,
+) -> Result<(), String> {
+    vault
+        .get()?
+        .remove_tag_implication(tag, implied_tag)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn list_tag_rules(vault: tauri::State<VaultState>) -> Result<Vec<TagRule>, String> {
+    vault.get()?.tag_rules().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn create_tag_rule(
+    vault: tauri::State<VaultState>,
+    rule: TagRuleInput,
+) -> Result<TagRule, String> {
+    vault
+        .get()?
+        .create_tag_rule(&rule)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn update_tag_rule(
+    vault: tauri::State<VaultState>,
+    rule_id: i64,
+    rule: TagRuleInput,
+) -> Result<TagRule, String> {
+    vault
+        .get()?
+        .update_tag_rule(rule_id, &rule)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn delete_tag_rule(vault: tauri::State<VaultState>, rule_id: i64) -> Result<(), String> {
+    vault
+        .get()?
+        .delete_tag_rule(rule_id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn preview_tag_rule(
+    vault: tauri::State<VaultState>,
+    rule: TagRuleInput,
+) -> Result<Vec<TagRuleMatch>, String> {
+    let vault = vault.get()?;
+    rule_utils::preview_rule(&vault, &rule).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn run_tag_rules(vault: tauri::State<VaultState>) -> Result<Vec<TagRuleMatch>, String> {
+    let vault = vault.get()?;
+    rule_utils::run_rules(&vault).map_err(|e| e.to_string())
+}
@@ -0,0 +1,192 @@
+use std::path::{Path, PathBuf};
+
+use crate::{state::VaultState, utils::path_utils};
+use tauri::{AppHandle, Emitter, Manager};
+use vault_core::{
+    Vault,
+    models::{
+        app_config::AppConfig,
+        vault::{VaultEntry, VaultInfo, VaultLocation},
+    },
+    utils::{config_utils, path_utils::DB_FILE, vault_utils},
+};
+
+fn current_vault_name(config: &AppConfig, root: &Path) -> String {
+    let active = config_utils::active_vault_name(config);
+    if config_utils::get_vault_path(config, &active).is_ok_and(|path| path == root) {
+        return active;
+    }
+
+    config
+        .vaults
+        .iter()
+        .find(|vault| Path::new(&vault.path) == root)
+        .map(|vault| vault.name.clone())
+        .unwrap_or_else(|| config_utils::DEFAULT_VAULT.to_string())
+}
+
+fn validate_vault_name(config: &AppConfig, name: &str) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err("Vault name cannot be empty".to_string());
+    }
+    if name == config_utils::DEFAULT_VAULT || config.vaults.iter().any(|vault| vault.name == name) {
+        return Err(format!("A vault named {} already exists", name));
+    }
+    Ok(())
+}
+
+fn activate_vault(
+    app: &AppHandle,
+    state: &VaultState,
+    name: &str,
+) -> Result<VaultLocation, String> {
+    let mut config = config_utils::load_config().map_err(|e| e.to_string())?;
+    let root = config_utils::get_vault_path(&config, name).map_err(|e| e.to_string())?;
+
+    let resource_dir = app.path().resource_dir().ok();
+    let vault = Vault::open(path_utils::vault_config(root, resource_dir.as_deref()))
+        .map_err(|e| e.to_string())?;
+    state.replace(Some(vault))?;
+
+    config.active_vault = Some(name.to_string());
+    config_utils::save_config(&config).map_err(|e| e.to_string())?;
+
+    app.emit("vault_changed", name).map_err(|e| e.to_string())?;
+
+    vault_location(state)
+}
+
+fn vault_location(state: &VaultState) -> Result<VaultLocation, String> {
+    let path = state.get()?.root().to_path_buf();
+    let config = config_utils::load_config().map_err(|e| e.to_string())?;
+    let name = current_vault_name(&config, &path);
+    let configured_path =
+        config_utils::get_vault_path(&config, &config_utils::active_vault_name(&config))
+            .ok()
+            .map(|configured| configured.to_string_lossy().to_string());
+
+    Ok(VaultLocation {
+        available: configured_path
+            .as_ref()
+            .is_some_and(|configured| Path::new(configured) == path),
+        name,
+        path: path.to_string_lossy().to_string(),
+        configured_path,
+        portable: config_utils::get_portable_dir().is_some(),
+    })
+}
+
+#[tauri::command]
+pub fn get_vault_location(state: tauri::State<VaultState>) -> Result<VaultLocation, String> {
+    vault_location(&state)
+}
+
+#[tauri::command]
+pub fn list_vaults(state: tauri::State<VaultState>) -> Result<Vec<VaultInfo>, String> {
+    let config = config_utils::load_config().map_err(|e| e.to_string())?;
+    let current = state
+        .get()
+        .ok()
+        .map(|vault| current_vault_name(&config, vault.root()));
+
+    let names = std::iter::once(config_utils::DEFAULT_VAULT.to_string())
+        .chain(config.vaults.iter().map(|vault| vault.name.clone()));
+
+    names
+        .map(|name| {
+            let path = config_utils::get_vault_path(&config, &name).map_err(|e| e.to_string())?;
+            Ok(VaultInfo {
+                active: current.as_deref() == Some(name.as_str()),
+                available: path.is_dir(),
+                path: path.to_string_lossy().to_string(),
+                name,
+            })
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub fn create_vault(name: &str, path: &str) -> Result<VaultInfo, String> {
+    let mut config = config_utils::load_config().map_err(|e| e.to_string())?;
+    validate_vault_name(&config, name)?;
+
+    Vault::create(path_utils::vault_config(PathBuf::from(path), None))
+        .map_err(|e| e.to_string())?;
+
+    config.vaults.push(VaultEntry {
+        name: name.to_string(),
+        path: path.to_string(),
+    });
+    config_utils::save_config(&config).map_err(|e| e.to_string())?;
+
+    Ok(VaultInfo {
+        name: name.to_string(),
+        path: path.to_string(),
+        active: false,
+        available: true,
+    })
+}
+
+#[tauri::command]
+pub fn open_vault(
+    app: AppHandle,
+    state: tauri::State<VaultState>,
+    name: &str,
+    path: &str,
+) -> Result<VaultLocation, String> {
+    let mut config = config_utils::load_config().map_err(|e| e.to_string())?;
+    validate_vault_name(&config, name)?;
+
+    if !Path::new(path).join(DB_FILE).exists() {
+        return Err(format!("{} does not contain a vault", path));
+    }
+
+    config.vaults.push(VaultEntry {
+        name: name.to_string(),
+        path: path.to_string(),
+    });
+    config_utils::save_config(&config).map_err(|e| e.to_string())?;
+
+    activate_vault(&app, &state, name)
+}
+
+#[tauri::command]
+pub fn switch_vault(
+    app: AppHandle,
+    state: tauri::State<VaultState>,
+    name: &str,
+) -> Result<VaultLocation, String> {
+    activate_vault(&app, &state, name)
+}
+
+#[tauri::command]
+pub fn close_vault(app: AppHandle, state: tauri::State<VaultState>) -> Result<(), String> {
+    state.replace(None)?;
+
+    app.emit("vault_changed", None::<String>)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn move_vault(
+    state: tauri::State<VaultState>,
+    destination: &str,
+) -> Result<VaultLocation, String> {
+    let vault = state.get()?;
+    let from = vault.root().to_path_buf();
+    let to = PathBuf::from(destination);
+
+    let moved = vault_utils::move_vault(&vault, &to).map_err(|e| e.to_string())?;
+
+    let mut config = config_utils::load_config().map_err(|e| e.to_string())?;
+    let name = current_vault_name(&config, &from);
+    config_utils::set_vault_path(&mut config, &name, &to);
+    config_utils::save_config(&config).map_err(|e| e.to_string())?;
+
+    state.replace(Some(moved))?;
+    drop(vault);
+
+    vault_utils::remove_vault_files(&from);
+
+    vault_location(&state)
+}
@@ -1,37 +1,60 @@
 pub mod commands;
-pub mod db;
-pub mod models;
+pub mod state;
 pub mod utils;
 
-use rusqlite::Connection;
-use std::sync::Mutex;
+use std::path::Path;
+
 use tauri::Manager;
+use vault_core::{Vault, utils::config_utils};
 
 use {
-    commands::image_commands,
-    db::database::{self, Db},
+    commands::{
+        album_commands, annotation_commands, api_commands, backup_commands, bulk_commands,
+        export_commands, history_commands, image_commands, settings_commands, tag_commands,
+        vault_commands,
+    },
+    state::{ApiServerState, VaultState},
     utils::path_utils,
 };
 
+fn open_startup_vault(resource_dir: Option<&Path>) -> Result<Vault, Box<dyn std::error::Error>> {
+    let vault_root = config_utils::resolve_vault_root().or_else(|e| {
+        let config = config_utils::load_config()?;
+        if config_utils::active_vault_name(&config) == config_utils::DEFAULT_VAULT {
+            return Err(e);
+        }
+        eprintln!("Warning: {}, falling back to the default vault", e);
+        config_utils::get_vault_path(&config, config_utils::DEFAULT_VAULT)
+    })?;
+    Vault::open(path_utils::vault_config(vault_root, resource_dir))
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .setup(|app| {
-            let db_path = path_utils::get_storage_path().join("imagevault.db");
-            let conn = Connection::open(db_path).expect("Failed to open DB");
+            let resource_dir = app
+                .path()
+                .resource_dir()
+                .inspect_err(|e| {
+                    eprintln!("Warning: Could not locate the resource directory: {}", e)
+                })
+                .ok();
 
-            for migration in database::MIGRATIONS {
-                conn.execute(migration, []).expect("Migration failed");
-            }
-
-            let resource_dir = app.path().resource_dir().unwrap();
-            println!("Resource directory: {:?}", resource_dir);
-            path_utils::set_resource_dir(Some(resource_dir));
+            let vault = open_startup_vault(resource_dir.as_deref()).map_err(|e| {
+                eprintln!("Warning: Could not open the vault: {}", e);
+                format!("Could not open the vault: {}", e)
+            });
 
-            database::sync_from_files(&conn)?;
-            database::run_folder_sync(&conn)?;
+            app.manage(VaultState::new(vault));
+            backup_commands::spawn_backup_scheduler(app.handle().clone());
 
-            app.manage(Db(Mutex::new(conn)));
+            app.manage(ApiServerState::default());
+            if let Err(e) =
+                tauri::async_runtime::block_on(api_commands::restart_api_server(app.handle()))
+            {
+                eprintln!("Warning: Could not start the API server: {}", e);
+            }
 
             Ok(())
         })
@@ -40,16 +63,96 @@ pub fn run() {
             image_commands::get_images,
             image_commands::add_tag,
             image_commands::get_tags,
+            image_commands::get_image_details,
             image_commands::search_images,
+            image_commands::set_image_rating,
+            image_commands::set_image_favorite,
+            image_commands::set_image_color_label,
             image_commands::ocr_retry,
             image_commands::remove_tag,
             image_commands::save_image_from_path,
             image_commands::save_image_from_folder,
+            image_commands::add_sync_folder,
             image_commands::delete_image,
             image_commands::copy_image_to_clipboard,
+            image_commands::copy_images_to_clipboard,
             image_commands::save_image_blob,
+            image_commands::paste_from_clipboard,
             image_commands::fetch_and_save_image,
             image_commands::fetch_and_save_from_file,
+            image_commands::preview_page_images,
+            image_commands::import_page_images,
+            image_commands::get_metadata_report,
+            export_commands::export_image,
+            export_commands::export_images,
+            settings_commands::get_settings,
+            settings_commands::update_settings,
+            settings_commands::get_network_settings,
+            settings_commands::update_network_settings,
+            backup_commands::create_backup,
+            backup_commands::restore_backup,
+            vault_commands::get_vault_location,
+            vault_commands::move_vault,
+            vault_commands::list_vaults,
+            vault_commands::create_vault,
+            vault_commands::open_vault,
+            vault_commands::switch_vault,
+            vault_commands::close_vault,
+            api_commands::get_api_settings,
+            api_commands::update_api_settings,
+            api_commands::regenerate_api_token,
+            album_commands::list_albums,
+            album_commands::get_image_albums,
+            album_commands::create_album,
+            album_commands::update_album,
+            album_commands::rename_album,
+            album_commands::delete_album,
+            album_commands::get_album_images,
+            album_commands::add_images_to_album,
+            album_commands::remove_images_from_album,
+            album_commands::reorder_album,
+            album_commands::list_album_folders,
+            album_commands::create_album_folder,
+            album_commands::rename_album_folder,
+            album_commands::move_album_folder,
+            album_commands::delete_album_folder,
+            album_commands::list_smart_albums,
+            album_commands::create_smart_album,
+            album_commands::update_smart_album,
+            album_commands::pin_smart_album,
+            album_commands::delete_smart_album,
+            album_commands::get_smart_album_images,
+            bulk_commands::add_tags_bulk,
+            bulk_commands::remove_tags_bulk,
+            bulk_commands::delete_images_bulk,
+            bulk_commands::ocr_retry_bulk,
+            bulk_commands::move_to_album_bulk,
+            tag_commands::list_tags,
+            tag_commands::autocomplete_tags,
+            tag_commands::rename_tag,
+            tag_commands::merge_tags,
+            tag_commands::get_unused_tags,
+            tag_commands::list_tag_aliases,
+            tag_commands::add_tag_alias,
+            tag_commands::remove_tag_alias,
+            tag_commands::list_tag_implications,
+            tag_commands::add_tag_implication,
+            tag_commands::remove_tag_implication,
+            tag_commands::list_tag_rules,
+            tag_commands::create_tag_rule,
+            tag_commands::update_tag_rule,
+            tag_commands::delete_tag_rule,
+            tag_commands::preview_tag_rule,
+            tag_commands::run_tag_rules,
+            history_commands::get_history,
+            history_commands::undo,
+            history_commands::redo,
+            annotation_commands::get_image_notes,
+            annotation_commands::set_image_notes,
+            annotation_commands::list_annotations,
+            annotation_commands::add_annotation,
+            annotation_commands::update_annotation,
+            annotation_commands::delete_annotation,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
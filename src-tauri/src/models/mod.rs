@@ -1,2 +0,0 @@
-pub mod image;
-pub mod sync_folder;
\ No newline at end of file
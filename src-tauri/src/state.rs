@@ -0,0 +1,49 @@
+use std::sync::{Arc, Mutex, RwLock};
+
+use tauri::{AppHandle, Manager};
+use vault_core::{
+    Vault,
+    utils::server_utils::{ApiServer, VaultProvider},
+};
+
+const NO_VAULT: &str = "No vault is open";
+
+pub struct VaultState(RwLock<Result<Arc<Vault>, String>>);
+
+impl VaultState {
+    pub fn new(vault: Result<Vault, String>) -> Self {
+        VaultState(RwLock::new(vault.map(Arc::new)))
+    }
+
+    pub fn get(&self) -> Result<Arc<Vault>, String> {
+        self.0.read().map_err(|e| e.to_string())?.clone()
+    }
+
+    pub fn replace(&self, vault: Option<Vault>) -> Result<(), String> {
+        *self.0.write().map_err(|e| e.to_string())? =
+            vault.map(Arc::new).ok_or_else(|| NO_VAULT.to_string());
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+pub struct ApiServerState(Mutex<Option<ApiServer>>);
+
+impl ApiServerState {
+    pub fn take(&self) -> Result<Option<ApiServer>, String> {
+        Ok(self.0.lock().map_err(|e| e.to_string())?.take())
+    }
+
+    pub fn set(&self, server: ApiServer) -> Result<(), String> {
+        *self.0.lock().map_err(|e| e.to_string())? = Some(server);
+        Ok(())
+    }
+}
+
+pub struct AppVault(pub AppHandle);
+
+impl VaultProvider for AppVault {
+    fn vault(&self) -> Result<Arc<Vault>, String> {
+        self.0.state::<VaultState>().get()
+    }
+}
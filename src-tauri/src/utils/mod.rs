@@ -1,2 +1 @@
 pub mod path_utils;
-pub mod image_utils;
\ No newline at end of file
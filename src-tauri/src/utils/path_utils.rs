@@ -1,33 +1,18 @@
-use dirs_next::data_dir;
-use std::path::PathBuf;
-use std::sync::Mutex;
+use std::path::{Path, PathBuf};
+use vault_core::{VaultConfig, utils::config_utils};
 
-pub static RESOURCE_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
-
-pub fn set_resource_dir(resource_dir: Option<PathBuf>) {
-    *RESOURCE_DIR.lock().unwrap() = resource_dir;
-}
-
-pub fn get_storage_path() -> PathBuf {
-    let mut path = data_dir().expect("No data dir found");
-    path.push("ImageVault");
-    std::fs::create_dir_all(&path).unwrap();
-    path
-}
-
-pub fn get_image_path() -> PathBuf {
-    let mut path = data_dir().expect("No data dir found");
-    path.push("ImageVault/Images");
-    std::fs::create_dir_all(&path).unwrap();
-    path
+pub fn get_tesseract_path(resource_dir: &Path) -> PathBuf {
+    resource_dir.join("tesseract").join("tesseract.exe")
 }
 
-pub fn get_tesseract_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
-    Ok(RESOURCE_DIR
-        .lock()
-        .unwrap()
-        .as_ref()
-        .ok_or("Could not find resources dir")?
-        .join("tesseract")
-        .join("tesseract.exe"))
+pub fn vault_config(root: PathBuf, resource_dir: Option<&Path>) -> VaultConfig {
+    VaultConfig {
+        root,
+        tesseract_path: resource_dir
+            .map(get_tesseract_path)
+            .filter(|path| path.exists()),
+        network: config_utils::load_config()
+            .map(|config| config.network)
+            .unwrap_or_default(),
+    }
 }